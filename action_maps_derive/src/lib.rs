@@ -0,0 +1,75 @@
+#![warn(clippy::all)]
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields};
+
+/// Implements `Actionlike` and `From<T> for Action` for an enum of unit variants. Each
+/// variant becomes an action named after the variant.
+#[proc_macro_derive(Actionlike)]
+pub fn derive_actionlike(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> Result<proc_macro2::TokenStream, Error> {
+    let ident = &input.ident;
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input,
+            "Actionlike can only be derived for enums",
+        ));
+    };
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "Actionlike can not be derived for generic enums",
+        ));
+    }
+
+    let mut variants = Vec::new();
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(
+                variant,
+                "Actionlike variants can not have fields",
+            ));
+        }
+        variants.push(&variant.ident);
+    }
+    let names = variants
+        .iter()
+        .map(|variant| variant.to_string())
+        .collect::<Vec<_>>();
+
+    Ok(quote! {
+        impl ::action_maps::actions::Actionlike for #ident {
+            fn variants() -> &'static [Self] {
+                &[#(#ident::#variants),*]
+            }
+
+            fn name(&self) -> &'static str {
+                match self {
+                    #(#ident::#variants => #names,)*
+                }
+            }
+
+            fn from_name(name: &str) -> ::core::option::Option<Self> {
+                match name {
+                    #(#names => ::core::option::Option::Some(#ident::#variants),)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+
+        impl ::core::convert::From<#ident> for ::action_maps::actions::Action {
+            fn from(value: #ident) -> Self {
+                ::action_maps::actions::Action::from(
+                    ::action_maps::actions::Actionlike::name(&value),
+                )
+            }
+        }
+    })
+}
@@ -37,7 +37,7 @@ impl From<Actions> for Action {
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
-        .add_plugins(ActionMapPlugin)
+        .add_plugins(ActionMapPlugin::new())
         .add_systems(PreStartup, setup)
         .add_systems(PreUpdate, handle_input.in_set(ActionMapSet::HandleActions))
         .run();
@@ -1,13 +1,152 @@
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::sync::RwLock;
+
+/// A type which can be used as the actions of an `ActionInput`, `ControlScheme` and
+/// `ActionMapPlugin`. This is implemented for `Action`, which is named at runtime, and
+/// for every `Actionlike` enum, which turns misspelled actions into compile errors.
+pub trait ActionType: Copy + Eq + Hash + Send + Sync + Debug + 'static {
+    /// The name the action is saved with.
+    fn action_name(&self) -> &'static str;
+
+    /// The action saved with `name`, if there is one.
+    fn from_action_name(name: &str) -> Option<Self>;
+}
+
+impl ActionType for Action {
+    fn action_name(&self) -> &'static str {
+        self.name()
+    }
+
+    fn from_action_name(name: &str) -> Option<Self> {
+        Some(Action::new(name))
+    }
+}
+
+/// A named action. Names are interned, so an `Action` is a cheap `Copy` id and two
+/// actions are equal exactly when their names are. Actions can be created from
+/// `&'static str`s, or at runtime from names loaded from data.
+/// ```rust
+/// use action_maps::prelude::*;
+///
+/// let jump = Action::from("Jump");
+/// let loaded = Action::new(&String::from("Jump"));
+/// assert_eq!(jump, loaded);
+/// assert_eq!(loaded.name(), "Jump");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Action {
-    pub name: &'static str,
+    id: u32,
+}
+
+impl Action {
+    /// Interns `name`, allocating it once the first time it is seen.
+    pub fn new(name: &str) -> Self {
+        if let Some(action) =
+            INTERNER.read().unwrap_or_else(|e| e.into_inner()).get(name)
+        {
+            return action;
+        }
+        INTERNER
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .intern(name, || Box::leak(name.to_owned().into_boxed_str()))
+    }
+
+    pub fn name(&self) -> &'static str {
+        INTERNER.read().unwrap_or_else(|e| e.into_inner()).names[self.id as usize]
+    }
+}
+
+impl std::fmt::Debug for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Action")
+            .field("name", &self.name())
+            .finish()
+    }
+}
+
+impl std::fmt::Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
 }
 
 impl From<&'static str> for Action {
     fn from(name: &'static str) -> Self {
-        Action { name }
+        if let Some(action) =
+            INTERNER.read().unwrap_or_else(|e| e.into_inner()).get(name)
+        {
+            return action;
+        }
+        // static names don't need to be copied
+        INTERNER
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .intern(name, || name)
     }
 }
 
-unsafe impl Send for Action {}
-unsafe impl Sync for Action {}
+impl From<String> for Action {
+    fn from(name: String) -> Self {
+        Action::new(&name)
+    }
+}
+
+impl From<&String> for Action {
+    fn from(name: &String) -> Self {
+        Action::new(name)
+    }
+}
+
+struct Interner {
+    names: Vec<&'static str>,
+    ids: Option<HashMap<&'static str, u32>>,
+}
+
+impl Interner {
+    fn get(&self, name: &str) -> Option<Action> {
+        let id = *self.ids.as_ref()?.get(name)?;
+        Some(Action { id })
+    }
+
+    fn intern(&mut self, name: &str, store: impl FnOnce() -> &'static str) -> Action {
+        // another thread may have interned the name since it was looked up
+        if let Some(action) = self.get(name) {
+            return action;
+        }
+        let id = self.names.len() as u32;
+        let name = store();
+        self.names.push(name);
+        self.ids.get_or_insert_with(HashMap::new).insert(name, id);
+        Action { id }
+    }
+}
+
+static INTERNER: RwLock<Interner> = RwLock::new(Interner {
+    names: Vec::new(),
+    ids: None,
+});
+
+/// Actions are serialized as their name.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Action {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.name())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Action {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let name = String::deserialize(deserializer)?;
+        Ok(Action::new(&name))
+    }
+}
@@ -1,6 +1,13 @@
-use crate::action::Action;
+use crate::action::{Action, ActionType};
+use crate::chord::Chord;
+use crate::input::UniversalInput;
+use crate::interaction::InteractionState;
+use crate::registry::QueriedActions;
+use bevy_ecs::component::Component;
 use bevy_ecs::system::Resource;
 use bevy_input::Input;
+use bevy_math::Vec2;
+use std::collections::{HashMap, HashSet};
 
 /// A wrapper around `bevy_input::Input` for use with the more generic `Action`s.
 /// The interface is exactly the same as `bevy_input::Input`. See `ControlScheme` for details on
@@ -15,89 +22,317 @@ use bevy_input::Input;
 ///    }
 /// }
 /// ```
-#[derive(Debug, Clone, Resource, Default)]
-pub struct ActionInput(Input<Action>);
+///
+/// Actions bound to an axis, such as a gamepad stick, additionally carry a signed value
+/// which can be read with `ActionInput::axis`.
+/// ```rust
+/// use bevy::prelude::*;
+/// use action_maps::prelude::*;
+///
+/// fn handle_input(input: Res<ActionInput>) {
+///    let throttle: f32 = input.axis("Throttle");
+/// }
+/// ```
+///
+/// Actions bound with a `DualAxis` carry a two dimensional value which can be read with
+/// `ActionInput::dual_axis`.
+///
+/// Actions bound with an `InputInteraction` are triggered for a single frame once the
+/// interaction completes, which can be read with `ActionInput::just_triggered`.
+///
+/// `ActionInput` and `ControlScheme` can also be components. The plugins update every
+/// entity which has both, so possessing another entity only moves the components.
+/// ```rust
+/// use bevy::prelude::*;
+/// use action_maps::prelude::*;
+///
+/// fn spawn(mut commands: Commands) {
+///     commands.spawn((make_controls!(("Up", KeyCode::W)), ActionInput::new()));
+/// }
+///
+/// fn handle_input(mut query: Query<(&ActionInput, &mut Transform)>) {
+///     for (input, mut transform) in query.iter_mut() {
+///         if input.pressed("Up") {
+///             transform.translation.y += 1.0;
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Resource, Component)]
+pub struct ActionInput<A: ActionType = Action> {
+    buttons: Input<A>,
+    axes: HashMap<A, f32>,
+    dual_axes: HashMap<A, Vec2>,
+    inputs: Input<UniversalInput>,
+    input_axes: HashMap<UniversalInput, f32>,
+    active_chords: Vec<(Chord, A)>,
+    triggered: HashSet<A>,
+    interactions: InteractionState<A>,
+    queried: QueriedActions<A>,
+}
+
+impl Default for ActionInput {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
 
 impl ActionInput {
-    pub fn press<A>(&mut self, input: A)
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<A: ActionType> ActionInput<A> {
+    /// An `ActionInput` with nothing pressed for any action type, such as
+    /// `ActionInput::<Actions>::empty()`.
+    pub fn empty() -> Self {
+        ActionInput {
+            buttons: Input::default(),
+            axes: HashMap::default(),
+            dual_axes: HashMap::default(),
+            inputs: Input::default(),
+            input_axes: HashMap::default(),
+            active_chords: Vec::default(),
+            triggered: HashSet::default(),
+            interactions: InteractionState::default(),
+            queried: QueriedActions::default(),
+        }
+    }
+
+    pub fn press<T>(&mut self, input: T)
     where
-        A: Into<Action>,
+        T: Into<A>,
     {
-        self.0.press(input.into());
+        self.buttons.press(input.into());
     }
 
-    pub fn pressed<A>(&self, input: A) -> bool
+    pub fn pressed<T>(&self, input: T) -> bool
     where
-        A: Into<Action>,
+        T: Into<A>,
     {
-        self.0.pressed(input.into())
+        let action = input.into();
+        self.queried.record(action);
+        self.buttons.pressed(action)
     }
 
-    pub fn any_pressed<A>(&self, inputs: impl IntoIterator<Item = A>) -> bool
+    pub fn any_pressed<T>(&self, inputs: impl IntoIterator<Item = T>) -> bool
     where
-        A: Into<Action>,
+        T: Into<A>,
     {
-        let inputs = inputs.into_iter().map(|a| a.into()).collect::<Vec<_>>();
-        self.0.any_pressed(inputs)
+        let inputs = self.record_all(inputs);
+        self.buttons.any_pressed(inputs)
     }
 
-    pub fn release<A>(&mut self, input: A)
+    pub fn release<T>(&mut self, input: T)
     where
-        A: Into<Action>,
+        T: Into<A>,
     {
-        self.0.release(input.into());
+        self.buttons.release(input.into());
     }
 
     pub fn release_all(&mut self) {
-        self.0.release_all();
+        self.buttons.release_all();
     }
 
-    pub fn just_pressed<A>(&self, input: A) -> bool
+    pub fn just_pressed<T>(&self, input: T) -> bool
     where
-        A: Into<Action>,
+        T: Into<A>,
     {
-        self.0.just_pressed(input.into())
+        let action = input.into();
+        self.queried.record(action);
+        self.buttons.just_pressed(action)
     }
 
-    pub fn any_just_pressed<A>(&self, inputs: impl IntoIterator<Item = A>) -> bool
+    pub fn any_just_pressed<T>(&self, inputs: impl IntoIterator<Item = T>) -> bool
     where
-        A: Into<Action>,
+        T: Into<A>,
     {
-        let inputs = inputs.into_iter().map(|a| a.into()).collect::<Vec<_>>();
-        self.0.any_just_pressed(inputs)
+        let inputs = self.record_all(inputs);
+        self.buttons.any_just_pressed(inputs)
     }
 
-    pub fn clear_just_pressed<A>(&mut self, input: A)
+    pub fn just_released<T>(&self, input: T) -> bool
     where
-        A: Into<Action>,
+        T: Into<A>,
     {
-        self.0.clear_just_pressed(input.into());
+        let action = input.into();
+        self.queried.record(action);
+        self.buttons.just_released(action)
     }
 
-    pub fn reset<A>(&mut self, input: A)
+    pub fn clear_just_pressed<T>(&mut self, input: T)
     where
-        A: Into<Action>,
+        T: Into<A>,
     {
-        self.0.reset(input.into());
+        self.buttons.clear_just_pressed(input.into());
+    }
+
+    pub fn reset<T>(&mut self, input: T)
+    where
+        T: Into<A>,
+    {
+        let action = input.into();
+        self.buttons.reset(action);
+        self.axes.remove(&action);
+        self.dual_axes.remove(&action);
     }
 
     pub fn reset_all(&mut self) {
-        self.0.reset_all();
+        self.buttons.reset_all();
+        self.axes.clear();
+        self.dual_axes.clear();
+        self.inputs.reset_all();
+        self.input_axes.clear();
+        self.active_chords.clear();
+        self.triggered.clear();
+        self.interactions = InteractionState::default();
     }
 
     pub fn clear(&mut self) {
-        self.0.clear();
+        self.buttons.clear();
+        self.inputs.clear();
+        self.triggered.clear();
+    }
+
+    pub fn get_pressed(&self) -> impl ExactSizeIterator<Item = &A> {
+        self.buttons.get_pressed()
+    }
+
+    pub fn get_just_pressed(&self) -> impl ExactSizeIterator<Item = &A> {
+        self.buttons.get_just_pressed()
+    }
+
+    pub fn get_just_released(&self) -> impl ExactSizeIterator<Item = &A> {
+        self.buttons.get_just_released()
+    }
+
+    /// Sets the axis value of an action. The value is kept until it is set again.
+    pub fn set_axis<T>(&mut self, input: T, value: f32)
+    where
+        T: Into<A>,
+    {
+        self.axes.insert(input.into(), value);
+    }
+
+    /// Returns the axis value of an action, or `0.0` if the axis has not been moved.
+    pub fn axis<T>(&self, input: T) -> f32
+    where
+        T: Into<A>,
+    {
+        let action = input.into();
+        self.queried.record(action);
+        self.axes.get(&action).copied().unwrap_or_default()
+    }
+
+    pub fn get_axes(&self) -> impl ExactSizeIterator<Item = (&A, &f32)> {
+        self.axes.iter()
+    }
+
+    /// Sets the two dimensional value of an action.
+    pub fn set_dual_axis<T>(&mut self, input: T, value: Vec2)
+    where
+        T: Into<A>,
+    {
+        self.dual_axes.insert(input.into(), value);
+    }
+
+    /// Returns the two dimensional value of an action bound with a `DualAxis`. Buttons
+    /// and sticks are clamped to a length of `1.0`, while mouse motion axes are
+    /// unclamped and added on top.
+    pub fn dual_axis<T>(&self, input: T) -> Vec2
+    where
+        T: Into<A>,
+    {
+        let action = input.into();
+        self.queried.record(action);
+        self.dual_axes.get(&action).copied().unwrap_or_default()
+    }
+
+    /// Returns `true` if an interaction bound to the action completed this frame.
+    pub fn just_triggered<T>(&self, input: T) -> bool
+    where
+        T: Into<A>,
+    {
+        let action = input.into();
+        self.queried.record(action);
+        self.triggered.contains(&action)
+    }
+
+    pub fn get_just_triggered(&self) -> impl ExactSizeIterator<Item = &A> {
+        self.triggered.iter()
+    }
+
+    pub fn trigger<T>(&mut self, input: T)
+    where
+        T: Into<A>,
+    {
+        self.triggered.insert(input.into());
+    }
+
+    fn record_all<T: Into<A>>(&self, inputs: impl IntoIterator<Item = T>) -> Vec<A> {
+        let inputs = inputs.into_iter().map(|a| a.into()).collect::<Vec<_>>();
+        for action in &inputs {
+            self.queried.record(*action);
+        }
+        inputs
+    }
+
+    pub(crate) fn queried(&self) -> &QueriedActions<A> {
+        &self.queried
+    }
+
+    pub(crate) fn interactions_mut(&mut self) -> &mut InteractionState<A> {
+        &mut self.interactions
+    }
+
+    /// Releases every action, so nothing stays held while input events are not being
+    /// dispatched. Held inputs stay held until their release event, so the OS repeats
+    /// of a key held across a context switch or a rebind don't press actions again.
+    pub(crate) fn release_everything(&mut self) {
+        self.buttons.release_all();
+        self.axes.clear();
+        self.dual_axes.clear();
+        self.input_axes.clear();
+        self.active_chords.clear();
+        self.interactions = InteractionState::default();
+    }
+
+    pub(crate) fn press_input(&mut self, input: UniversalInput) {
+        self.inputs.press(input);
+    }
+
+    pub(crate) fn release_input(&mut self, input: UniversalInput) {
+        self.inputs.release(input);
+    }
+
+    pub(crate) fn input_pressed(&self, input: UniversalInput) -> bool {
+        self.inputs.pressed(input)
+    }
+
+    pub(crate) fn set_input_axis(&mut self, input: UniversalInput, value: f32) {
+        self.input_axes.insert(input, value);
     }
 
-    pub fn get_pressed(&self) -> impl ExactSizeIterator<Item = &Action> {
-        self.0.get_pressed()
+    pub(crate) fn input_axis(&self, input: UniversalInput) -> f32 {
+        self.input_axes.get(&input).copied().unwrap_or_default()
     }
 
-    pub fn get_just_pressed(&self) -> impl ExactSizeIterator<Item = &Action> {
-        self.0.get_just_pressed()
+    pub(crate) fn activate_chord(&mut self, chord: Chord, action: A) {
+        self.active_chords.push((chord, action));
     }
 
-    pub fn get_just_released(&self) -> impl ExactSizeIterator<Item = &Action> {
-        self.0.get_just_released()
+    /// Removes every active chord which `input` is a part of, returning their actions.
+    pub(crate) fn deactivate_chords(&mut self, input: UniversalInput) -> Vec<A> {
+        let mut actions = Vec::new();
+        self.active_chords.retain(|(chord, action)| {
+            let is_part =
+                chord.trigger() == input || chord.modifiers().contains(&input);
+            if is_part {
+                actions.push(*action);
+            }
+            !is_part
+        });
+        actions
     }
 }
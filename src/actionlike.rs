@@ -0,0 +1,53 @@
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use crate::action::{Action, ActionType};
+
+/// An enum of actions. With the `derive` feature, this is implemented with
+/// `#[derive(Actionlike)]`, which also implements `From<T> for Action`.
+/// ```rust
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use action_maps::prelude::*;
+///
+/// #[derive(Actionlike, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// enum Actions {
+///     Jump,
+///     Crouch,
+/// }
+///
+/// assert_eq!(Action::from(Actions::Jump).name(), "Jump");
+/// assert_eq!(Actions::from_name("Crouch"), Some(Actions::Crouch));
+/// assert_eq!(Actions::variants(), &[Actions::Jump, Actions::Crouch]);
+/// # }
+/// ```
+pub trait Actionlike: Sized + Copy + 'static {
+    /// Every variant, in declaration order.
+    fn variants() -> &'static [Self];
+
+    /// The name of the action this variant converts to.
+    fn name(&self) -> &'static str;
+
+    fn from_name(name: &str) -> Option<Self>;
+
+    fn from_action(action: Action) -> Option<Self> {
+        Self::from_name(action.name())
+    }
+
+    fn action(&self) -> Action {
+        Action::from(self.name())
+    }
+}
+
+impl<T> ActionType for T
+where
+    T: Actionlike + Eq + Hash + Send + Sync + Debug,
+{
+    fn action_name(&self) -> &'static str {
+        self.name()
+    }
+
+    fn from_action_name(name: &str) -> Option<Self> {
+        Self::from_name(name)
+    }
+}
@@ -0,0 +1,128 @@
+use bevy_app::{App, Plugin, PreUpdate, Startup};
+use bevy_asset::io::Reader;
+use bevy_asset::{
+    Asset, AssetApp, AssetEvent, AssetServer, Assets, AsyncReadExt, BoxedFuture,
+    Handle, LoadContext,
+};
+use bevy_ecs::event::EventReader;
+use bevy_ecs::schedule::IntoSystemConfigs;
+use bevy_ecs::system::{Commands, Res, ResMut, Resource};
+use bevy_reflect::TypePath;
+use thiserror::Error;
+
+use crate::control_scheme::ControlScheme;
+use crate::plugin::ActionMapSet;
+
+/// A `ControlScheme` loaded from a `.controls.ron` file. The file contains a serialized
+/// `ControlScheme`.
+/// ```ron
+/// (
+///     inputs: {
+///         "Jump": [Space, GamepadSouth(0)],
+///     },
+/// )
+/// ```
+#[derive(Asset, TypePath, Debug, Clone, PartialEq)]
+pub struct ControlSchemeAsset(pub ControlScheme);
+
+#[derive(Debug, Error)]
+pub enum ControlSchemeLoaderError {
+    #[error("Could not read control scheme: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse control scheme: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+}
+
+/// Loads `ControlSchemeAsset`s from files with the `.controls.ron` extension. Plain
+/// `.ron` files are left to other loaders.
+#[derive(Default)]
+pub struct ControlSchemeLoader;
+
+impl bevy_asset::AssetLoader for ControlSchemeLoader {
+    type Asset = ControlSchemeAsset;
+    type Settings = ();
+    type Error = ControlSchemeLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a Self::Settings,
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let controls = ron::de::from_bytes::<ControlScheme>(&bytes)?;
+            Ok(ControlSchemeAsset(controls))
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["controls.ron"]
+    }
+}
+
+/// Registers the `ControlSchemeAsset` and its loader. This is added by the
+/// `ActionMapPlugin` when it is given a control scheme asset, but can be added on its
+/// own to load control schemes manually.
+pub struct ControlSchemeAssetPlugin;
+
+impl Plugin for ControlSchemeAssetPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<ControlSchemeAsset>()
+            .init_asset_loader::<ControlSchemeLoader>();
+    }
+}
+
+/// The control scheme asset which is applied to the `ControlScheme` resource whenever
+/// it is loaded or modified, replacing the bindings changed at runtime.
+#[derive(Resource, Debug, Clone)]
+pub struct ActiveControlSchemeAsset(pub Handle<ControlSchemeAsset>);
+
+#[derive(Resource)]
+struct ControlSchemeAssetPath(String);
+
+pub(crate) fn build(app: &mut App, path: &str) {
+    if !app.is_plugin_added::<ControlSchemeAssetPlugin>() {
+        app.add_plugins(ControlSchemeAssetPlugin);
+    }
+
+    app.insert_resource(ControlSchemeAssetPath(path.to_string()))
+        .add_systems(Startup, load_control_scheme_asset)
+        .add_systems(
+            PreUpdate,
+            apply_control_scheme_asset.before(ActionMapSet::ReadEvents),
+        );
+}
+
+fn load_control_scheme_asset(
+    mut commands: Commands,
+    path: Res<ControlSchemeAssetPath>,
+    asset_server: Res<AssetServer>,
+) {
+    let handle = asset_server.load(path.0.clone());
+    commands.insert_resource(ActiveControlSchemeAsset(handle));
+}
+
+fn apply_control_scheme_asset(
+    mut asset_events: EventReader<AssetEvent<ControlSchemeAsset>>,
+    active: Option<Res<ActiveControlSchemeAsset>>,
+    assets: Res<Assets<ControlSchemeAsset>>,
+    mut control_scheme: ResMut<ControlScheme>,
+) {
+    let Some(active) = active else {
+        return;
+    };
+
+    for event in asset_events.read() {
+        let (AssetEvent::Added { id } | AssetEvent::Modified { id }) = event else {
+            continue;
+        };
+        if *id != active.0.id() {
+            continue;
+        }
+        if let Some(asset) = assets.get(*id) {
+            control_scheme.set(asset.0.clone());
+        }
+    }
+}
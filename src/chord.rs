@@ -0,0 +1,56 @@
+use crate::input::UniversalInput;
+
+/// A binding made of any number of modifier inputs and a trigger input. The chord is
+/// pressed when the trigger is pressed while every modifier is held. A chord takes
+/// priority over an action bound to its bare trigger, so binding "Save" to Ctrl+S does
+/// not also fire the action bound to S.
+/// ```rust
+/// use bevy::prelude::*;
+/// use action_maps::prelude::*;
+///
+/// fn bind_keys(mut control_scheme: ResMut<ControlScheme>) {
+///     control_scheme.insert("Down", KeyCode::S);
+///     control_scheme.insert_chord("Save", Chord::new([KeyCode::ControlLeft], KeyCode::S));
+///     control_scheme.insert_chord(
+///         "Parry",
+///         Chord::new(
+///             [GamepadButton::new(Gamepad::new(0), GamepadButtonType::LeftTrigger)],
+///             GamepadButton::new(Gamepad::new(0), GamepadButtonType::South),
+///         ),
+///     );
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "crate::serialization::ChordData"))]
+pub struct Chord {
+    modifiers: Vec<UniversalInput>,
+    trigger: UniversalInput,
+}
+
+impl Chord {
+    pub fn new<M, I, T>(modifiers: M, trigger: T) -> Self
+    where
+        M: IntoIterator<Item = I>,
+        I: Into<UniversalInput>,
+        T: Into<UniversalInput>,
+    {
+        let mut modifiers = modifiers.into_iter().map(Into::into).collect::<Vec<_>>();
+        // modifiers are unordered, so keep them sorted for comparisons
+        modifiers.sort_unstable();
+        modifiers.dedup();
+
+        Chord {
+            modifiers,
+            trigger: trigger.into(),
+        }
+    }
+
+    pub fn modifiers(&self) -> &[UniversalInput] {
+        &self.modifiers
+    }
+
+    pub fn trigger(&self) -> UniversalInput {
+        self.trigger
+    }
+}
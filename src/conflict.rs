@@ -0,0 +1,277 @@
+use std::collections::BTreeMap;
+
+use crate::action::{Action, ActionType};
+use crate::chord::Chord;
+use crate::control_scheme::ControlScheme;
+use crate::gamepad_slot::PLAYER_GAMEPAD;
+use crate::input::UniversalInput;
+use crate::interaction::InputInteraction;
+use crate::multi_scheme::MultiScheme;
+
+/// Two bindings in a `ControlScheme` which react to the same input. Both the binding
+/// made with `insert` and those made with `insert_shared` are checked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BindingConflict<A: ActionType = Action> {
+    /// The trigger of a chord is also bound on its own. The chord takes priority while
+    /// its modifiers are held.
+    ChordTrigger {
+        chord: Chord,
+        chord_action: A,
+        action: A,
+    },
+    /// A modifier of a chord is also bound on its own, so holding the modifier fires
+    /// `action` before the chord is pressed.
+    ChordModifier {
+        chord: Chord,
+        chord_action: A,
+        modifier: UniversalInput,
+        action: A,
+    },
+    /// An input with an interaction is also bound on its own to a different action.
+    Interaction {
+        input: UniversalInput,
+        interaction: InputInteraction,
+        interaction_action: A,
+        action: A,
+    },
+    /// A shared binding consumes an input before `action`, which is bound to the same
+    /// input with a lower priority, so `action` never fires from it.
+    Consumed {
+        input: UniversalInput,
+        consuming_action: A,
+        action: A,
+    },
+}
+
+impl<A: ActionType> BindingConflict<A> {
+    /// The input both bindings react to.
+    pub fn input(&self) -> UniversalInput {
+        match self {
+            BindingConflict::ChordTrigger { chord, .. } => chord.trigger(),
+            BindingConflict::ChordModifier { modifier, .. } => *modifier,
+            BindingConflict::Interaction { input, .. } => *input,
+            BindingConflict::Consumed { input, .. } => *input,
+        }
+    }
+}
+
+/// An input used by the control schemes of several players in a `MultiScheme`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerConflict {
+    pub input: UniversalInput,
+    /// The ids of the players using the input, sorted.
+    pub players: Vec<usize>,
+}
+
+impl<A: ActionType> ControlScheme<A> {
+    /// Lists the bindings which react to the same input, sorted by input.
+    /// ```rust
+    /// use bevy::prelude::*;
+    /// use action_maps::prelude::*;
+    /// use action_maps::controls::BindingConflict;
+    ///
+    /// let mut controls = ControlScheme::new();
+    /// controls.insert("Down", ScanCode(0x1F));
+    /// controls.insert_chord("Save", Chord::new([ScanCode(0x1D)], ScanCode(0x1F)));
+    ///
+    /// let conflicts = controls.conflicts();
+    /// assert_eq!(conflicts.len(), 1);
+    /// assert!(matches!(conflicts[0], BindingConflict::ChordTrigger { .. }));
+    /// ```
+    pub fn conflicts(&self) -> Vec<BindingConflict<A>> {
+        let mut conflicts = Vec::new();
+
+        for (chord, chord_action) in self.iter_chords() {
+            for action in self.actions_for(chord.trigger()) {
+                conflicts.push(BindingConflict::ChordTrigger {
+                    chord: chord.clone(),
+                    chord_action: *chord_action,
+                    action,
+                });
+            }
+            for modifier in chord.modifiers() {
+                for action in self.actions_for(*modifier) {
+                    conflicts.push(BindingConflict::ChordModifier {
+                        chord: chord.clone(),
+                        chord_action: *chord_action,
+                        modifier: *modifier,
+                        action,
+                    });
+                }
+            }
+        }
+
+        for (input, bindings) in self.iter_interactions() {
+            for action in self.actions_for(*input) {
+                for (interaction, interaction_action) in bindings {
+                    if *interaction_action != action {
+                        conflicts.push(BindingConflict::Interaction {
+                            input: *input,
+                            interaction: *interaction,
+                            interaction_action: *interaction_action,
+                            action,
+                        });
+                    }
+                }
+            }
+        }
+
+        for input in self.iter_shared().map(|(input, _)| *input) {
+            let bindings = self.bindings_for(input);
+            let Some(consumed_at) = bindings.iter().position(|binding| binding.consume)
+            else {
+                continue;
+            };
+            let consuming_action = bindings[consumed_at].action;
+            let fired = self.actions_for(input);
+            for binding in &bindings[consumed_at + 1..] {
+                if !fired.contains(&binding.action) {
+                    conflicts.push(BindingConflict::Consumed {
+                        input,
+                        consuming_action,
+                        action: binding.action,
+                    });
+                }
+            }
+        }
+
+        conflicts.sort_by_key(BindingConflict::input);
+        conflicts
+    }
+
+    /// Every input this scheme reacts to.
+    pub(crate) fn bound_inputs(&self) -> Vec<UniversalInput> {
+        let mut inputs = self.iter().map(|(input, _)| *input).collect::<Vec<_>>();
+        for (chord, _) in self.iter_chords() {
+            inputs.push(chord.trigger());
+            inputs.extend_from_slice(chord.modifiers());
+        }
+        inputs.extend(self.iter_interactions().map(|(input, _)| *input));
+        inputs.extend(self.iter_shared().map(|(input, _)| *input));
+        for (_, bindings) in self.iter_dual_axes() {
+            inputs.extend(bindings.iter().flat_map(|binding| binding.inputs()));
+        }
+        inputs.sort_unstable();
+        inputs.dedup();
+        inputs
+    }
+}
+
+impl MultiScheme {
+    /// Lists the inputs used by more than one player, sorted by input. Inputs on
+    /// `PLAYER_GAMEPAD` are skipped, since each player reads their own gamepad.
+    pub fn conflicts(&self) -> Vec<PlayerConflict> {
+        let mut users = BTreeMap::<UniversalInput, Vec<usize>>::new();
+        for id in self.keys() {
+            let Some(controls) = self.get(*id) else {
+                continue;
+            };
+            for input in controls.bound_inputs() {
+                if input.gamepad() == Some(PLAYER_GAMEPAD.id) {
+                    continue;
+                }
+                users.entry(input).or_default().push(*id);
+            }
+        }
+
+        users
+            .into_iter()
+            .filter(|(_, players)| players.len() > 1)
+            .map(|(input, mut players)| {
+                players.sort_unstable();
+                PlayerConflict { input, players }
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test_shared_binding_conflicts() {
+    use crate::control_scheme::SharedBinding;
+    use bevy::prelude::ScanCode;
+
+    let space = ScanCode(0x39);
+    let mut controls = ControlScheme::new();
+    controls.insert("Jump", space);
+    controls.insert_shared(space, SharedBinding::new("Confirm"));
+    controls.insert_shared(
+        ScanCode(0x01),
+        SharedBinding::new("Pause").with_priority(1).consuming(),
+    );
+    controls.insert_shared(ScanCode(0x01), SharedBinding::new("Back"));
+    controls.insert_chord("Shout", Chord::new([ScanCode(0x2A)], space));
+
+    let conflicts = controls.conflicts();
+    assert_eq!(
+        conflicts,
+        vec![
+            BindingConflict::Consumed {
+                input: ScanCode(0x01).into(),
+                consuming_action: Action::from("Pause"),
+                action: Action::from("Back"),
+            },
+            BindingConflict::ChordTrigger {
+                chord: Chord::new([ScanCode(0x2A)], space),
+                chord_action: Action::from("Shout"),
+                action: Action::from("Jump"),
+            },
+            BindingConflict::ChordTrigger {
+                chord: Chord::new([ScanCode(0x2A)], space),
+                chord_action: Action::from("Shout"),
+                action: Action::from("Confirm"),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_multi_scheme_conflicts() {
+    use crate::make_controls;
+    use bevy::prelude::{Gamepad, GamepadButton, GamepadButtonType, ScanCode};
+
+    let south = |id| GamepadButton::new(Gamepad::new(id), GamepadButtonType::South);
+    let mut ms = MultiScheme::new();
+    ms.insert(
+        0,
+        make_controls!(("Jump", ScanCode(0x39)), ("Jump", south(0))),
+    );
+    ms.insert(
+        1,
+        make_controls!(("Jump", ScanCode(0x39)), ("Jump", south(1))),
+    );
+    let mut controls = make_controls!(("Up", ScanCode(0x11)));
+    controls.insert_chord("Shout", Chord::new([ScanCode(0x2A)], ScanCode(0x39)));
+    ms.insert(2, controls);
+
+    assert_eq!(
+        ms.conflicts(),
+        vec![PlayerConflict {
+            input: ScanCode(0x39).into(),
+            players: vec![0, 1, 2],
+        }]
+    );
+}
+
+#[test]
+fn test_player_gamepad_is_not_a_conflict() {
+    use crate::make_controls;
+    use bevy::prelude::{
+        GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType,
+    };
+
+    let controls = make_controls!(
+        (
+            "Jump",
+            GamepadButton::new(PLAYER_GAMEPAD, GamepadButtonType::South)
+        ),
+        (
+            "MoveX",
+            GamepadAxis::new(PLAYER_GAMEPAD, GamepadAxisType::LeftStickX)
+        ),
+    );
+    let mut ms = MultiScheme::new();
+    ms.insert(0, controls.clone());
+    ms.insert(1, controls);
+
+    assert_eq!(ms.conflicts(), vec![]);
+}
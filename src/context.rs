@@ -0,0 +1,329 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::marker::PhantomData;
+
+use bevy_app::App;
+use bevy_ecs::change_detection::{DetectChanges, DetectChangesMut};
+use bevy_ecs::event::{Event, EventReader};
+use bevy_ecs::schedule::{OnEnter, OnExit, States};
+use bevy_ecs::system::{Res, ResMut, Resource, SystemParam};
+use bevy_log::warn;
+
+use crate::action::{Action, ActionType};
+use crate::control_scheme::ControlScheme;
+
+/// Whether the contexts below an `ActionContext` on the stack still receive input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ContextMode {
+    /// Only this context and the ones above it receive input.
+    #[default]
+    Block,
+    /// Inputs which this context doesn't use pass through to the contexts below it.
+    Passthrough,
+}
+
+/// A named set of bindings which is active while it is on the `ActionContexts` stack.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActionContext<A: ActionType = Action> {
+    pub controls: ControlScheme<A>,
+    pub mode: ContextMode,
+}
+
+impl<A: ActionType> ActionContext<A> {
+    /// A context which hides the contexts below it, such as a pause menu.
+    pub fn blocking(controls: ControlScheme<A>) -> Self {
+        ActionContext {
+            controls,
+            mode: ContextMode::Block,
+        }
+    }
+
+    /// A context which only takes the inputs it uses, such as an inventory which still
+    /// lets the player walk around.
+    pub fn passthrough(controls: ControlScheme<A>) -> Self {
+        ActionContext {
+            controls,
+            mode: ContextMode::Passthrough,
+        }
+    }
+}
+
+/// A stack of `ActionContext`s on top of the `ControlScheme` resource. The context on
+/// top gets the first pick of every input, and each context below it receives the
+/// remaining inputs until a blocking context is reached. The `ControlScheme` resource
+/// is always at the bottom of the stack, so rebinds made to it are kept while other
+/// contexts come and go.
+/// ```rust
+/// use bevy::prelude::*;
+/// use action_maps::prelude::*;
+/// use action_maps::controls::ActionContext;
+///
+/// fn setup(mut contexts: ResMut<ActionContexts>) {
+///     contexts.insert(
+///         "Pause",
+///         ActionContext::blocking(make_controls!(("Resume", KeyCode::Escape))),
+///     );
+/// }
+///
+/// fn pause(mut contexts: ResMut<ActionContexts>, input: Res<ActionInput>) {
+///     if input.just_pressed("Pause") {
+///         contexts.push("Pause");
+///     }
+/// }
+/// ```
+#[derive(Resource, Debug, Clone)]
+pub struct ActionContexts<A: ActionType = Action> {
+    contexts: HashMap<String, ActionContext<A>>,
+    stack: Vec<String>,
+    merged: Option<ControlScheme<A>>,
+    merged_stack: Vec<String>,
+    release_pending: bool,
+}
+
+impl<A: ActionType> Default for ActionContexts<A> {
+    fn default() -> Self {
+        ActionContexts {
+            contexts: HashMap::default(),
+            stack: Vec::default(),
+            merged: None,
+            merged_stack: Vec::default(),
+            release_pending: false,
+        }
+    }
+}
+
+impl<A: ActionType> ActionContexts<A> {
+    /// Adds a context which can be pushed by name, replacing the context with the same
+    /// name.
+    pub fn insert<S>(&mut self, name: S, context: ActionContext<A>)
+    where
+        S: Into<String>,
+    {
+        self.contexts.insert(name.into(), context);
+    }
+
+    /// Removes a context, and takes it off the stack.
+    pub fn remove(&mut self, name: &str) -> Option<ActionContext<A>> {
+        self.stack.retain(|pushed| pushed != name);
+        self.contexts.remove(name)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ActionContext<A>> {
+        self.contexts.get(name)
+    }
+
+    /// The context with `name`, for example to rebind its controls.
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut ActionContext<A>> {
+        self.contexts.get_mut(name)
+    }
+
+    pub fn push<S>(&mut self, name: S)
+    where
+        S: Into<String>,
+    {
+        self.stack.push(name.into());
+    }
+
+    pub fn pop(&mut self) -> Option<String> {
+        self.stack.pop()
+    }
+
+    /// Takes the context with `name` off the stack, wherever it is.
+    pub fn remove_from_stack(&mut self, name: &str) {
+        self.stack.retain(|pushed| pushed != name);
+    }
+
+    /// The name of the context on top of the stack.
+    pub fn top(&self) -> Option<&str> {
+        self.stack.last().map(String::as_str)
+    }
+
+    /// The names of the contexts on the stack, from the bottom to the top.
+    pub fn stack(&self) -> &[String] {
+        &self.stack
+    }
+
+    pub fn is_active(&self, name: &str) -> bool {
+        self.stack.iter().any(|pushed| pushed == name)
+    }
+
+    /// Whether a blocking context on the stack hides the bindings below all contexts.
+    pub(crate) fn blocks_base(&self) -> bool {
+        self.stack.iter().any(|name| {
+            self.contexts
+                .get(name)
+                .is_some_and(|context| context.mode == ContextMode::Block)
+        })
+    }
+
+    /// Merges the contexts on the stack from the top down onto `base`.
+    fn merge(&self, base: &ControlScheme<A>) -> ControlScheme<A> {
+        let mut merged = ControlScheme::empty();
+        for name in self.stack.iter().rev() {
+            let Some(context) = self.contexts.get(name) else {
+                warn!("Action context {name} is on the stack but was never inserted");
+                continue;
+            };
+            merged.merge_below(&context.controls);
+            if context.mode == ContextMode::Block {
+                return merged;
+            }
+        }
+        merged.merge_below(base);
+        merged
+    }
+}
+
+/// Pushes and pops `ActionContexts` from anywhere which can send events. Each action
+/// type has its own events, so `ContextEvent<Actions>` only changes the
+/// `ActionContexts<Actions>` stack.
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub enum ContextEvent<A: ActionType = Action> {
+    Push(String),
+    Pop,
+    #[doc(hidden)]
+    _Marker(Infallible, PhantomData<fn() -> A>),
+}
+
+/// Merges the stack whenever it or the `ControlScheme` changes. When the stack changes,
+/// every held action is released by the input system, after it clears the previous
+/// frame, so nothing stays pressed after the context which pressed it is gone and the
+/// release shows up in `ActionInput::get_just_released`.
+pub(crate) fn update_action_contexts<A: ActionType>(
+    mut events: EventReader<ContextEvent<A>>,
+    mut contexts: ResMut<ActionContexts<A>>,
+    control_scheme: Res<ControlScheme<A>>,
+) {
+    for event in events.read() {
+        match event {
+            ContextEvent::Push(name) => contexts.push(name.clone()),
+            ContextEvent::Pop => {
+                contexts.pop();
+            }
+            ContextEvent::_Marker(never, _) => match *never {},
+        }
+    }
+
+    if contexts.is_changed() || control_scheme.is_changed() {
+        let merged =
+            (!contexts.stack.is_empty()).then(|| contexts.merge(&control_scheme));
+        let contexts = contexts.bypass_change_detection();
+        contexts.merged = merged;
+        if contexts.merged_stack != contexts.stack {
+            contexts.merged_stack = contexts.stack.clone();
+            contexts.release_pending = true;
+        }
+    }
+}
+
+/// The bindings input events are dispatched with: the merged `ActionContexts` if any
+/// are on the stack, otherwise the `ControlScheme` resource.
+#[derive(SystemParam)]
+pub struct ActiveControls<'w, A: ActionType> {
+    control_scheme: Res<'w, ControlScheme<A>>,
+    contexts: Option<ResMut<'w, ActionContexts<A>>>,
+}
+
+impl<A: ActionType> ActiveControls<'_, A> {
+    /// Whether the context stack changed since this was last asked, so held actions
+    /// should be released.
+    pub(crate) fn take_release(&mut self) -> bool {
+        self.contexts.as_mut().is_some_and(|contexts| {
+            std::mem::take(&mut contexts.bypass_change_detection().release_pending)
+        })
+    }
+
+    pub fn get(&self) -> &ControlScheme<A> {
+        self.contexts
+            .as_ref()
+            .and_then(|contexts| contexts.merged.as_ref())
+            .unwrap_or(&self.control_scheme)
+    }
+}
+
+/// The name of the `ActionContext` added for `state` by
+/// `StateContextAppExt::add_state_context`: the type name of the `States` enum followed
+/// by the `Debug` output of `state`.
+/// ```rust
+/// use bevy::prelude::*;
+/// use action_maps::controls::state_context_name;
+///
+/// #[derive(States, Debug, Clone, PartialEq, Eq, Hash, Default)]
+/// enum GameState {
+///     #[default]
+///     Paused,
+/// }
+///
+/// assert!(state_context_name(&GameState::Paused).ends_with("GameState::Paused"));
+/// ```
+pub fn state_context_name<S: States>(state: &S) -> String {
+    format!("{}::{state:?}", std::any::type_name::<S>())
+}
+
+/// Ties `ActionContext`s to the values of a Bevy `States` enum.
+pub trait StateContextAppExt {
+    /// Pushes `context` onto the `ActionContexts` stack when `state` is entered, and
+    /// takes it off when `state` is exited. The context is named with
+    /// `state_context_name`, so the same variant of two `States` enums gets two
+    /// contexts.
+    /// ```rust
+    /// use bevy::prelude::*;
+    /// use action_maps::prelude::*;
+    /// use action_maps::controls::ActionContext;
+    ///
+    /// #[derive(States, Debug, Clone, PartialEq, Eq, Hash, Default)]
+    /// enum GameState {
+    ///     #[default]
+    ///     InGame,
+    ///     Paused,
+    /// }
+    ///
+    /// App::new()
+    ///     .add_plugins(ActionMapPlugin::new())
+    ///     .add_state::<GameState>()
+    ///     .add_state_context(
+    ///         GameState::Paused,
+    ///         ActionContext::blocking(make_controls!(("Resume", KeyCode::Escape))),
+    ///     );
+    /// ```
+    fn add_state_context<S, A>(
+        &mut self,
+        state: S,
+        context: ActionContext<A>,
+    ) -> &mut Self
+    where
+        S: States,
+        A: ActionType;
+}
+
+impl StateContextAppExt for App {
+    fn add_state_context<S, A>(
+        &mut self,
+        state: S,
+        context: ActionContext<A>,
+    ) -> &mut Self
+    where
+        S: States,
+        A: ActionType,
+    {
+        let name = state_context_name(&state);
+        self.init_resource::<ActionContexts<A>>();
+        self.world
+            .resource_mut::<ActionContexts<A>>()
+            .insert(name.clone(), context);
+
+        let pushed = name.clone();
+        self.add_systems(
+            OnEnter(state.clone()),
+            move |mut contexts: ResMut<ActionContexts<A>>| {
+                contexts.push(pushed.clone())
+            },
+        )
+        .add_systems(
+            OnExit(state),
+            move |mut contexts: ResMut<ActionContexts<A>>| {
+                contexts.remove_from_stack(&name)
+            },
+        )
+    }
+}
@@ -1,8 +1,12 @@
+use bevy_ecs::component::Component;
 use bevy_ecs::system::Resource;
 use std::collections::HashMap;
 
-use crate::action::Action;
+use crate::action::{Action, ActionType};
+use crate::chord::Chord;
+use crate::dual_axis::DualAxis;
 use crate::input::UniversalInput;
+use crate::interaction::InputInteraction;
 
 /// A wrapper around a map of `Action`s to `UniversalInput`s.  
 /// Example:
@@ -17,61 +21,391 @@ use crate::input::UniversalInput;
 ///    control_scheme.insert("Shoot", MouseButton::Left);
 /// }
 /// ```
-#[derive(Debug, Clone, Resource, Default, PartialEq, Eq)]
-pub struct ControlScheme(HashMap<UniversalInput, Action>);
+#[derive(Debug, Clone, Resource, Component, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "crate::serialization::ControlSchemeData",
+        into = "crate::serialization::ControlSchemeData",
+        bound = ""
+    )
+)]
+pub struct ControlScheme<A: ActionType = Action> {
+    inputs: HashMap<UniversalInput, A>,
+    chords: HashMap<Chord, A>,
+    interactions: HashMap<UniversalInput, Vec<(InputInteraction, A)>>,
+    dual_axes: HashMap<A, Vec<DualAxis>>,
+    shared: HashMap<UniversalInput, Vec<SharedBinding<A>>>,
+}
+
+impl Default for ControlScheme {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
 
-#[allow(dead_code)]
 impl ControlScheme {
-    pub fn set(&mut self, other: ControlScheme) {
-        self.0 = other.0;
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[allow(dead_code)]
+impl<A: ActionType> ControlScheme<A> {
+    /// A scheme without bindings for any action type, such as
+    /// `ControlScheme::<Actions>::empty()`.
+    pub fn empty() -> Self {
+        ControlScheme {
+            inputs: HashMap::default(),
+            chords: HashMap::default(),
+            interactions: HashMap::default(),
+            dual_axes: HashMap::default(),
+            shared: HashMap::default(),
+        }
     }
 
-    pub fn insert<A, I>(&mut self, action: A, input: I)
+    pub fn set(&mut self, other: ControlScheme<A>) {
+        *self = other;
+    }
+
+    /// Binds `input` to `action`. Each input has at most one binding made with `insert`,
+    /// so if the input was bound to a different action, that action is unbound and
+    /// returned. Use `insert_shared` to bind more actions to an input alongside it.
+    pub fn insert<T, I>(&mut self, action: T, input: I) -> Option<A>
     where
-        A: Into<Action>,
+        T: Into<A>,
         I: Into<UniversalInput>,
     {
-        self.0.insert(input.into(), action.into());
+        let action = action.into();
+        self.inputs
+            .insert(input.into(), action)
+            .filter(|displaced| *displaced != action)
     }
 
     pub fn remove<I>(&mut self, input: I)
     where
         I: Into<UniversalInput>,
     {
-        self.0.remove(&input.into());
+        self.inputs.remove(&input.into());
     }
 
-    pub fn get<I>(&self, input: I) -> Option<&Action>
+    pub fn get<I>(&self, input: I) -> Option<&A>
     where
         I: Into<UniversalInput>,
     {
-        self.0.get(&input.into())
+        self.inputs.get(&input.into())
     }
 
-    pub fn get_mut<I>(&mut self, input: I) -> Option<&mut Action>
+    pub fn get_mut<I>(&mut self, input: I) -> Option<&mut A>
     where
         I: Into<UniversalInput>,
     {
-        self.0.get_mut(&input.into())
+        self.inputs.get_mut(&input.into())
     }
 
     pub fn contains_key<I>(&self, input: I) -> bool
     where
         I: Into<UniversalInput>,
     {
-        self.0.contains_key(&input.into())
+        self.inputs.contains_key(&input.into())
     }
 
     pub fn clear(&mut self) {
-        self.0.clear();
+        self.inputs.clear();
+        self.chords.clear();
+        self.interactions.clear();
+        self.dual_axes.clear();
+        self.shared.clear();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&UniversalInput, &A)> {
+        self.inputs.iter()
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = (&UniversalInput, &Action)> {
-        self.0.iter()
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&UniversalInput, &mut A)> {
+        self.inputs.iter_mut()
     }
 
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&UniversalInput, &mut Action)> {
-        self.0.iter_mut()
+    /// Binds a `Chord` to an action. When a chord is pressed, it takes priority over the
+    /// action bound to its bare trigger.
+    pub fn insert_chord<T>(&mut self, action: T, chord: Chord)
+    where
+        T: Into<A>,
+    {
+        self.chords.insert(chord, action.into());
+    }
+
+    pub fn remove_chord(&mut self, chord: &Chord) {
+        self.chords.remove(chord);
+    }
+
+    pub fn get_chord(&self, chord: &Chord) -> Option<&A> {
+        self.chords.get(chord)
+    }
+
+    pub fn iter_chords(&self) -> impl Iterator<Item = (&Chord, &A)> {
+        self.chords.iter()
+    }
+
+    /// Finds the chord triggered by `trigger` given which inputs are held. When several
+    /// chords match, the one with the most modifiers wins.
+    pub(crate) fn matching_chord(
+        &self,
+        trigger: UniversalInput,
+        is_held: impl Fn(UniversalInput) -> bool,
+    ) -> Option<(&Chord, &A)> {
+        self.chords
+            .iter()
+            .filter(|(chord, _)| chord.trigger() == trigger)
+            .filter(|(chord, _)| chord.modifiers().iter().all(|m| is_held(*m)))
+            .max_by(|(a, _), (b, _)| {
+                (a.modifiers().len(), a).cmp(&(b.modifiers().len(), b))
+            })
+    }
+
+    /// Binds an action to an input with an `InputInteraction`. An input may have several
+    /// interactions, such as a tap and a hold bound to different actions.
+    pub fn insert_interaction<T, I>(
+        &mut self,
+        action: T,
+        input: I,
+        interaction: InputInteraction,
+    ) where
+        T: Into<A>,
+        I: Into<UniversalInput>,
+    {
+        let binding = (interaction, action.into());
+        let bindings = self.interactions.entry(input.into()).or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+    }
+
+    /// Removes all interactions bound to an input.
+    pub fn remove_interactions<I>(&mut self, input: I)
+    where
+        I: Into<UniversalInput>,
+    {
+        self.interactions.remove(&input.into());
+    }
+
+    pub fn get_interactions<I>(&self, input: I) -> &[(InputInteraction, A)]
+    where
+        I: Into<UniversalInput>,
+    {
+        self.interactions
+            .get(&input.into())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn iter_interactions(
+        &self,
+    ) -> impl Iterator<Item = (&UniversalInput, &Vec<(InputInteraction, A)>)> {
+        self.interactions.iter()
+    }
+
+    /// Adds a two dimensional binding to an action. An action may have any number of
+    /// `DualAxis` bindings, the values of which are summed together.
+    pub fn insert_dual_axis<T>(&mut self, action: T, binding: DualAxis)
+    where
+        T: Into<A>,
+    {
+        let bindings = self.dual_axes.entry(action.into()).or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+    }
+
+    /// Removes all `DualAxis` bindings from an action.
+    pub fn remove_dual_axis<T>(&mut self, action: T)
+    where
+        T: Into<A>,
+    {
+        self.dual_axes.remove(&action.into());
+    }
+
+    pub fn get_dual_axes<T>(&self, action: T) -> &[DualAxis]
+    where
+        T: Into<A>,
+    {
+        self.dual_axes
+            .get(&action.into())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn iter_dual_axes(&self) -> impl Iterator<Item = (&A, &Vec<DualAxis>)> {
+        self.dual_axes.iter()
+    }
+
+    /// Binds an action to an input in addition to the actions already bound to it, such
+    /// as Space being both "Jump" and "Confirm". Actions bound with `insert` have a
+    /// priority of `0` and don't consume the input. Binding the same action to the input
+    /// again replaces its `SharedBinding`.
+    /// ```rust
+    /// use bevy::prelude::*;
+    /// use action_maps::prelude::*;
+    /// use action_maps::controls::SharedBinding;
+    ///
+    /// let mut controls = ControlScheme::new();
+    /// controls.insert("Jump", KeyCode::Space);
+    /// controls.insert_shared(KeyCode::Space, SharedBinding::new("Confirm").with_priority(1));
+    /// controls.insert_shared(KeyCode::Space, SharedBinding::new("Skip").with_priority(-1));
+    ///
+    /// assert_eq!(
+    ///     controls.actions_for(KeyCode::Space),
+    ///     vec![Action::from("Confirm"), Action::from("Jump"), Action::from("Skip")],
+    /// );
+    /// ```
+    pub fn insert_shared<I>(&mut self, input: I, binding: SharedBinding<A>)
+    where
+        I: Into<UniversalInput>,
+    {
+        let bindings = self.shared.entry(input.into()).or_default();
+        match bindings.iter_mut().find(|b| b.action == binding.action) {
+            Some(existing) => *existing = binding,
+            None => bindings.push(binding),
+        }
+    }
+
+    /// Removes the `SharedBinding` of an action from an input.
+    pub fn remove_shared<I, T>(&mut self, input: I, action: T)
+    where
+        I: Into<UniversalInput>,
+        T: Into<A>,
+    {
+        let input = input.into();
+        let action = action.into();
+        if let Some(bindings) = self.shared.get_mut(&input) {
+            bindings.retain(|binding| binding.action != action);
+            if bindings.is_empty() {
+                self.shared.remove(&input);
+            }
+        }
+    }
+
+    pub fn get_shared<I>(&self, input: I) -> &[SharedBinding<A>]
+    where
+        I: Into<UniversalInput>,
+    {
+        self.shared
+            .get(&input.into())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn iter_shared(
+        &self,
+    ) -> impl Iterator<Item = (&UniversalInput, &Vec<SharedBinding<A>>)> {
+        self.shared.iter()
+    }
+
+    /// The actions `input` fires, from the highest priority to the lowest. Ties keep the
+    /// action bound with `insert` first, then shared bindings in the order they were
+    /// added. The list stops after the first binding which consumes the input.
+    pub fn actions_for<I>(&self, input: I) -> Vec<A>
+    where
+        I: Into<UniversalInput>,
+    {
+        let bindings = self.bindings_for(input.into());
+        let mut actions = Vec::with_capacity(bindings.len());
+        for binding in bindings {
+            if !actions.contains(&binding.action) {
+                actions.push(binding.action);
+            }
+            if binding.consume {
+                break;
+            }
+        }
+        actions
+    }
+
+    /// Every binding of `input`, including the one made with `insert`, in the order
+    /// `actions_for` fires them.
+    pub(crate) fn bindings_for(&self, input: UniversalInput) -> Vec<SharedBinding<A>> {
+        let mut bindings = self
+            .get(input)
+            .map(|action| SharedBinding {
+                action: *action,
+                priority: 0,
+                consume: false,
+            })
+            .into_iter()
+            .chain(self.get_shared(input).iter().copied())
+            .collect::<Vec<_>>();
+        // stable, so ties keep their order
+        bindings.sort_by_key(|binding| std::cmp::Reverse(binding.priority));
+        bindings
+    }
+
+    /// Adds the bindings of `lower` which don't use an input this scheme already reacts
+    /// to, so this scheme gets the first pick of every input.
+    pub(crate) fn merge_below(&mut self, lower: &ControlScheme<A>) {
+        let taken = self.bound_inputs();
+        let is_free = |input: &UniversalInput| taken.binary_search(input).is_err();
+
+        for (input, action) in lower.iter() {
+            if is_free(input) {
+                self.inputs.insert(*input, *action);
+            }
+        }
+        for (chord, action) in lower.iter_chords() {
+            if is_free(&chord.trigger()) && chord.modifiers().iter().all(is_free) {
+                self.chords.insert(chord.clone(), *action);
+            }
+        }
+        for (input, bindings) in lower.iter_interactions() {
+            if is_free(input) {
+                self.interactions.insert(*input, bindings.clone());
+            }
+        }
+        for (input, bindings) in lower.iter_shared() {
+            if is_free(input) {
+                self.shared.insert(*input, bindings.clone());
+            }
+        }
+        for (action, bindings) in lower.iter_dual_axes() {
+            for binding in bindings {
+                if binding.inputs().iter().all(is_free) {
+                    self.insert_dual_axis(*action, *binding);
+                }
+            }
+        }
+    }
+}
+
+/// An action bound with `ControlScheme::insert_shared`. Higher priorities fire first,
+/// and a binding which consumes its input stops the lower priority actions from firing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SharedBinding<A: ActionType = Action> {
+    pub action: A,
+    pub priority: i32,
+    pub consume: bool,
+}
+
+impl<A: ActionType> SharedBinding<A> {
+    pub fn new<T>(action: T) -> Self
+    where
+        T: Into<A>,
+    {
+        SharedBinding {
+            action: action.into(),
+            priority: 0,
+            consume: false,
+        }
+    }
+
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Stops the actions with a lower priority from firing.
+    pub fn consuming(mut self) -> Self {
+        self.consume = true;
+        self
     }
 }
 
@@ -81,7 +415,7 @@ impl ControlScheme {
 /// use action_maps::prelude::*;
 /// use bevy::prelude::*;
 ///
-/// let mut control_scheme = ControlScheme::default();
+/// let mut control_scheme = ControlScheme::new();
 /// control_scheme.insert("A", KeyCode::A);
 /// control_scheme.insert("W", KeyCode::W);
 ///
@@ -96,7 +430,7 @@ impl ControlScheme {
 macro_rules! make_controls {
     ( $( ($A: expr, $I: expr) $(,)? ),*) => {
         {
-            let mut controls = ControlScheme::default();
+            let mut controls = ControlScheme::new();
             $(
                 controls.insert($A, $I);
             )*
@@ -109,8 +443,8 @@ macro_rules! make_controls {
 fn test_make_controls() {
     use bevy::prelude::KeyCode;
 
-    let mut cs = ControlScheme::default();
-    let mut cs_t = ControlScheme::default();
+    let mut cs = ControlScheme::new();
+    let mut cs_t = ControlScheme::new();
 
     cs.insert("A", KeyCode::A);
     cs.insert("W", KeyCode::W);
@@ -119,3 +453,37 @@ fn test_make_controls() {
 
     assert_eq!(cs, cs_t);
 }
+
+#[test]
+fn test_shared_bindings() {
+    use bevy::prelude::ScanCode;
+
+    let space = ScanCode(0x39);
+    let mut controls = ControlScheme::new();
+    controls.insert("Jump", space);
+    controls.insert_shared(space, SharedBinding::new("Confirm"));
+    controls.insert_shared(space, SharedBinding::new("Fire").with_priority(-1));
+    assert_eq!(
+        controls.actions_for(space),
+        vec![
+            Action::from("Jump"),
+            Action::from("Confirm"),
+            Action::from("Fire")
+        ]
+    );
+
+    // a consuming binding stops everything below it
+    controls.insert_shared(
+        space,
+        SharedBinding::new("Confirm").with_priority(1).consuming(),
+    );
+    assert_eq!(controls.actions_for(space), vec![Action::from("Confirm")]);
+
+    controls.remove_shared(space, "Confirm");
+    assert_eq!(
+        controls.actions_for(space),
+        vec![Action::from("Jump"), Action::from("Fire")]
+    );
+    // `insert` still replaces the action bound with it
+    assert_eq!(controls.insert("Crouch", space), Some(Action::from("Jump")));
+}
@@ -0,0 +1,140 @@
+use bevy_input::gamepad::Gamepad;
+use bevy_math::Vec2;
+
+use crate::action::ActionType;
+use crate::action_input::ActionInput;
+use crate::input::UniversalInput;
+
+/// A composite binding which combines several inputs into a single two dimensional
+/// action. Any number of `DualAxis` bindings can feed the same action, so the keyboard
+/// and a gamepad stick can drive the same movement.
+/// ```rust
+/// use bevy::prelude::*;
+/// use action_maps::prelude::*;
+///
+/// fn bind_keys(mut control_scheme: ResMut<ControlScheme>) {
+///     control_scheme.insert_dual_axis(
+///         "Move",
+///         DualAxis::buttons(KeyCode::W, KeyCode::S, KeyCode::A, KeyCode::D),
+///     );
+///     control_scheme.insert_dual_axis("Move", DualAxis::left_stick(Gamepad::new(0)));
+/// }
+///
+/// fn handle_input(input: Res<ActionInput>) {
+///     let direction: Vec2 = input.dual_axis("Move");
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DualAxis {
+    /// Four button-like inputs, one for each direction.
+    Buttons {
+        up: UniversalInput,
+        down: UniversalInput,
+        left: UniversalInput,
+        right: UniversalInput,
+    },
+    /// A pair of axis inputs, such as the two axes of a gamepad stick.
+    Axes {
+        x: UniversalInput,
+        y: UniversalInput,
+    },
+}
+
+impl DualAxis {
+    pub fn buttons<U, D, L, R>(up: U, down: D, left: L, right: R) -> Self
+    where
+        U: Into<UniversalInput>,
+        D: Into<UniversalInput>,
+        L: Into<UniversalInput>,
+        R: Into<UniversalInput>,
+    {
+        DualAxis::Buttons {
+            up: up.into(),
+            down: down.into(),
+            left: left.into(),
+            right: right.into(),
+        }
+    }
+
+    pub fn axes<X, Y>(x: X, y: Y) -> Self
+    where
+        X: Into<UniversalInput>,
+        Y: Into<UniversalInput>,
+    {
+        DualAxis::Axes {
+            x: x.into(),
+            y: y.into(),
+        }
+    }
+
+    pub fn left_stick(gamepad: Gamepad) -> Self {
+        DualAxis::Axes {
+            x: UniversalInput::GamepadLeftStickX(gamepad.id),
+            y: UniversalInput::GamepadLeftStickY(gamepad.id),
+        }
+    }
+
+    pub fn right_stick(gamepad: Gamepad) -> Self {
+        DualAxis::Axes {
+            x: UniversalInput::GamepadRightStickX(gamepad.id),
+            y: UniversalInput::GamepadRightStickY(gamepad.id),
+        }
+    }
+
+    /// The movement of the mouse this frame, in pixels. Unlike the other bindings, its
+    /// value isn't clamped to a length of 1.
+    pub fn mouse_motion() -> Self {
+        DualAxis::Axes {
+            x: UniversalInput::MouseMotionX,
+            y: UniversalInput::MouseMotionY,
+        }
+    }
+
+    /// The inputs which make up this binding.
+    pub fn inputs(&self) -> Vec<UniversalInput> {
+        match *self {
+            DualAxis::Buttons {
+                up,
+                down,
+                left,
+                right,
+            } => vec![up, down, left, right],
+            DualAxis::Axes { x, y } => vec![x, y],
+        }
+    }
+
+    /// Whether the value of this binding is limited to a length of 1. Mouse motion is a
+    /// distance rather than a direction, so it is left as it is.
+    pub(crate) fn is_bounded(&self) -> bool {
+        !self.inputs().iter().any(|input| {
+            matches!(
+                input,
+                UniversalInput::MouseMotionX | UniversalInput::MouseMotionY
+            )
+        })
+    }
+
+    /// The current, unnormalized value of this binding.
+    pub(crate) fn value<A: ActionType>(&self, action_input: &ActionInput<A>) -> Vec2 {
+        let button = |input: UniversalInput| -> f32 {
+            if action_input.input_pressed(input) {
+                1.
+            } else {
+                0.
+            }
+        };
+
+        match *self {
+            DualAxis::Buttons {
+                up,
+                down,
+                left,
+                right,
+            } => Vec2::new(button(right) - button(left), button(up) - button(down)),
+            DualAxis::Axes { x, y } => {
+                Vec2::new(action_input.input_axis(x), action_input.input_axis(y))
+            }
+        }
+    }
+}
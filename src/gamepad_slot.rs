@@ -0,0 +1,54 @@
+use bevy_ecs::component::Component;
+use bevy_ecs::event::EventReader;
+use bevy_ecs::system::{Res, ResMut};
+use bevy_input::gamepad::{Gamepad, GamepadConnection, GamepadConnectionEvent};
+
+use crate::lobby::Lobby;
+use crate::multi_input::MultiInput;
+
+/// Stands for the gamepad assigned to the player reading a binding. Bindings made with
+/// it follow the player's `GamepadSlot`, so a `MultiScheme` doesn't need to be rewritten
+/// when controllers are plugged in in a different order.
+/// ```rust
+/// use bevy::prelude::*;
+/// use action_maps::multiplayer_prelude::*;
+///
+/// let controls = make_controls!(
+///     ("Jump", GamepadButton::new(PLAYER_GAMEPAD, GamepadButtonType::South)),
+///     ("MoveX", GamepadAxis::new(PLAYER_GAMEPAD, GamepadAxisType::LeftStickX)),
+/// );
+/// ```
+///
+/// Its id fits into the signed integers of formats such as TOML, so schemes using it can
+/// be saved.
+pub const PLAYER_GAMEPAD: Gamepad = Gamepad {
+    id: u32::MAX as usize,
+};
+
+/// The gamepad assigned to a player in `MultiInput`. A slot outlives a disconnect, so
+/// the player gets a gamepad back when one is reconnected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct GamepadSlot {
+    pub gamepad: Gamepad,
+    pub connected: bool,
+}
+
+/// Assigns gamepads as they connect. While a `Lobby` exists, players only get a gamepad
+/// by joining with it, whatever `MultiInput::auto_assign_gamepads` is set to.
+pub(crate) fn assign_gamepads(
+    mut events: EventReader<GamepadConnectionEvent>,
+    mut multi_input: ResMut<MultiInput>,
+    lobby: Option<Res<Lobby>>,
+) {
+    let auto_assign = multi_input.auto_assign_gamepads() && lobby.is_none();
+    for event in events.read() {
+        match event.connection {
+            GamepadConnection::Connected(_) => {
+                multi_input.connect(event.gamepad, auto_assign)
+            }
+            GamepadConnection::Disconnected => {
+                multi_input.disconnect_gamepad(event.gamepad)
+            }
+        }
+    }
+}
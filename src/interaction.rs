@@ -0,0 +1,189 @@
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use crate::action::ActionType;
+use crate::input::UniversalInput;
+
+/// Describes how an input has to be pressed for a binding to trigger its action.
+/// Triggered actions are read with `ActionInput::just_triggered`, and are only
+/// triggered for the single frame in which the interaction completed.
+/// ```rust
+/// use std::time::Duration;
+/// use bevy::prelude::*;
+/// use action_maps::prelude::*;
+///
+/// fn bind_keys(mut control_scheme: ResMut<ControlScheme>) {
+///     control_scheme.insert_interaction(
+///         "Reload",
+///         KeyCode::R,
+///         InputInteraction::tap(Duration::from_millis(200)),
+///     );
+///     control_scheme.insert_interaction(
+///         "Inspect",
+///         KeyCode::R,
+///         InputInteraction::hold(Duration::from_millis(500)),
+///     );
+///     control_scheme.insert_interaction(
+///         "Dash",
+///         KeyCode::D,
+///         InputInteraction::double_tap(Duration::from_millis(250)),
+///     );
+/// }
+///
+/// fn handle_input(input: Res<ActionInput>) {
+///     if input.just_triggered("Reload") {
+///         println!("Reloading!");
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InputInteraction {
+    /// Triggers when the input is released no later than `max_duration` after it was
+    /// pressed.
+    Tap { max_duration: Duration },
+    /// Triggers once the input has been held for `min_duration`, while it is still held.
+    Hold { min_duration: Duration },
+    /// Triggers when the input is released after being held for at least
+    /// `min_duration`.
+    LongPress { min_duration: Duration },
+    /// Triggers when the input is pressed `taps` times, each press no later than
+    /// `window` after the previous one.
+    MultiTap { taps: u32, window: Duration },
+}
+
+impl InputInteraction {
+    pub fn tap(max_duration: Duration) -> Self {
+        InputInteraction::Tap { max_duration }
+    }
+
+    pub fn hold(min_duration: Duration) -> Self {
+        InputInteraction::Hold { min_duration }
+    }
+
+    pub fn long_press(min_duration: Duration) -> Self {
+        InputInteraction::LongPress { min_duration }
+    }
+
+    pub fn double_tap(window: Duration) -> Self {
+        InputInteraction::MultiTap { taps: 2, window }
+    }
+
+    pub fn multi_tap(taps: u32, window: Duration) -> Self {
+        InputInteraction::MultiTap { taps, window }
+    }
+}
+
+/// Keeps track of press timings for inputs with interactions.
+#[derive(Debug, Clone)]
+pub(crate) struct InteractionState<A> {
+    pressed_at: HashMap<UniversalInput, Duration>,
+    taps: HashMap<UniversalInput, (u32, Duration)>,
+    fired_holds: HashSet<(UniversalInput, A)>,
+}
+
+impl<A> Default for InteractionState<A> {
+    fn default() -> Self {
+        InteractionState {
+            pressed_at: HashMap::default(),
+            taps: HashMap::default(),
+            fired_holds: HashSet::default(),
+        }
+    }
+}
+
+impl<A: ActionType> InteractionState<A> {
+    /// Records a press, returning the actions of any completed multi-taps.
+    pub(crate) fn press(
+        &mut self,
+        input: UniversalInput,
+        now: Duration,
+        bindings: &[(InputInteraction, A)],
+    ) -> Vec<A> {
+        self.pressed_at.insert(input, now);
+
+        let multi_taps = bindings
+            .iter()
+            .filter_map(|(interaction, action)| match interaction {
+                InputInteraction::MultiTap { taps, window } => {
+                    Some((*taps, *window, *action))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let Some(max_taps) = multi_taps.iter().map(|(taps, ..)| *taps).max() else {
+            return Vec::new();
+        };
+        let window = multi_taps.iter().map(|(_, window, _)| *window).max();
+
+        let count = match (self.taps.get(&input), window) {
+            (Some((count, last)), Some(window)) if now - *last <= window => count + 1,
+            _ => 1,
+        };
+
+        // start counting again once the longest multi-tap is complete
+        if count >= max_taps {
+            self.taps.remove(&input);
+        } else {
+            self.taps.insert(input, (count, now));
+        }
+
+        multi_taps
+            .into_iter()
+            .filter(|(taps, ..)| *taps == count)
+            .map(|(.., action)| action)
+            .collect()
+    }
+
+    /// Records a release, returning the actions of any completed taps and long presses.
+    pub(crate) fn release(
+        &mut self,
+        input: UniversalInput,
+        now: Duration,
+        bindings: &[(InputInteraction, A)],
+    ) -> Vec<A> {
+        self.fired_holds.retain(|(held, _)| *held != input);
+        let Some(pressed_at) = self.pressed_at.remove(&input) else {
+            return Vec::new();
+        };
+        let held_for = now - pressed_at;
+
+        bindings
+            .iter()
+            .filter(|(interaction, _)| match interaction {
+                InputInteraction::Tap { max_duration } => held_for <= *max_duration,
+                InputInteraction::LongPress { min_duration } => {
+                    held_for >= *min_duration
+                }
+                _ => false,
+            })
+            .map(|(_, action)| *action)
+            .collect()
+    }
+
+    /// Checks a held input, returning the actions of any holds which completed since
+    /// the last check.
+    pub(crate) fn hold(
+        &mut self,
+        input: UniversalInput,
+        now: Duration,
+        bindings: &[(InputInteraction, A)],
+    ) -> Vec<A> {
+        let Some(pressed_at) = self.pressed_at.get(&input) else {
+            return Vec::new();
+        };
+        let held_for = now - *pressed_at;
+
+        let mut triggered = Vec::new();
+        for (interaction, action) in bindings {
+            if let InputInteraction::Hold { min_duration } = interaction {
+                if held_for >= *min_duration
+                    && self.fired_holds.insert((input, *action))
+                {
+                    triggered.push(*action);
+                }
+            }
+        }
+        triggered
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+
+use bevy_ecs::event::{Event, EventWriter};
+use bevy_ecs::system::{Res, ResMut, Resource};
+use bevy_input::gamepad::{Gamepad, GamepadButton, GamepadSettings};
+use bevy_input::ButtonState;
+
+use crate::action::Action;
+use crate::control_scheme::ControlScheme;
+use crate::gamepad_slot::PLAYER_GAMEPAD;
+use crate::multi_input::MultiInput;
+use crate::multi_scheme::MultiScheme;
+use crate::universal_input::{InputEventReaders, UniversalInput};
+
+/// A device a player joined the `Lobby` with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JoinDevice {
+    Gamepad(Gamepad),
+    /// A part of the keyboard, by the index of its template in the `Lobby`.
+    Keyboard(usize),
+}
+
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub enum LobbyEvent {
+    Joined { player: usize, device: JoinDevice },
+    Left { player: usize, device: JoinDevice },
+}
+
+/// Lets players join a local multiplayer game by pressing the join action on any device
+/// which no one is using yet. Joining allocates the lowest free player id, inserts a
+/// copy of the device's template into the `MultiScheme` and sends a
+/// `LobbyEvent::Joined`. Pressing the leave action removes the player again.
+///
+/// Each keyboard template is a separate device, so two players can share a keyboard.
+/// The gamepad template is bound with `PLAYER_GAMEPAD`. While a `Lobby` exists,
+/// gamepads are only assigned to players by joining. `MultiInput::auto_assign_gamepads`
+/// is left as it is, and applies again once the `Lobby` is removed.
+/// ```rust
+/// use bevy::prelude::*;
+/// use action_maps::multiplayer_prelude::*;
+///
+/// let lobby = Lobby::new("Join", "Leave")
+///     .with_gamepad_template(make_controls!(
+///         ("Join", GamepadButton::new(PLAYER_GAMEPAD, GamepadButtonType::Start)),
+///         ("Leave", GamepadButton::new(PLAYER_GAMEPAD, GamepadButtonType::Select)),
+///         ("Jump", GamepadButton::new(PLAYER_GAMEPAD, GamepadButtonType::South)),
+///     ))
+///     .with_keyboard_template(make_controls!(
+///         ("Join", KeyCode::E),
+///         ("Leave", KeyCode::Q),
+///         ("Jump", KeyCode::W),
+///     ))
+///     .with_keyboard_template(make_controls!(
+///         ("Join", KeyCode::ShiftRight),
+///         ("Leave", KeyCode::Delete),
+///         ("Jump", KeyCode::Up),
+///     ))
+///     .with_max_players(4);
+///
+/// App::new()
+///     .add_plugins(MultiActionMapPlugin)
+///     .insert_resource(lobby);
+/// ```
+#[derive(Resource, Debug, Clone)]
+pub struct Lobby {
+    join: Action,
+    leave: Action,
+    gamepad_template: Option<ControlScheme>,
+    keyboard_templates: Vec<ControlScheme>,
+    max_players: Option<usize>,
+    players: HashMap<usize, JoinDevice>,
+}
+
+impl Lobby {
+    pub fn new<J, L>(join: J, leave: L) -> Self
+    where
+        J: Into<Action>,
+        L: Into<Action>,
+    {
+        Lobby {
+            join: join.into(),
+            leave: leave.into(),
+            gamepad_template: None,
+            keyboard_templates: Vec::new(),
+            max_players: None,
+            players: HashMap::new(),
+        }
+    }
+
+    /// The controls of players who join with a gamepad.
+    pub fn with_gamepad_template(mut self, template: ControlScheme) -> Self {
+        self.gamepad_template = Some(template);
+        self
+    }
+
+    /// Adds a part of the keyboard which one player can join with.
+    pub fn with_keyboard_template(mut self, template: ControlScheme) -> Self {
+        self.keyboard_templates.push(template);
+        self
+    }
+
+    /// Stops players from joining once `MultiInput` has `max_players` players, including
+    /// the ones which were added without joining.
+    pub fn with_max_players(mut self, max_players: usize) -> Self {
+        self.max_players = Some(max_players);
+        self
+    }
+
+    /// The device a player joined with.
+    pub fn device(&self, player: usize) -> Option<JoinDevice> {
+        self.players.get(&player).copied()
+    }
+
+    /// The player using a device.
+    pub fn player(&self, device: JoinDevice) -> Option<usize> {
+        self.players
+            .iter()
+            .find(|(_, joined)| **joined == device)
+            .map(|(player, _)| *player)
+    }
+
+    pub fn players(&self) -> impl Iterator<Item = (usize, JoinDevice)> + '_ {
+        self.players
+            .iter()
+            .map(|(player, device)| (*player, *device))
+    }
+
+    fn is_full(&self, multi_input: &MultiInput) -> bool {
+        self.max_players
+            .is_some_and(|max_players| multi_input.keys().len() >= max_players)
+    }
+
+    fn template(&self, device: JoinDevice) -> Option<&ControlScheme> {
+        match device {
+            JoinDevice::Gamepad(_) => self.gamepad_template.as_ref(),
+            JoinDevice::Keyboard(index) => self.keyboard_templates.get(index),
+        }
+    }
+
+    fn is_join(&self, device: JoinDevice, input: UniversalInput) -> bool {
+        self.template(device)
+            .is_some_and(|template| template.actions_for(input).contains(&self.join))
+    }
+}
+
+pub(crate) fn lobby_system(
+    mut event_readers: InputEventReaders,
+    mut lobby: ResMut<Lobby>,
+    mut multi_input: ResMut<MultiInput>,
+    mut multi_scheme: ResMut<MultiScheme>,
+    mut lobby_events: EventWriter<LobbyEvent>,
+    settings: Res<GamepadSettings>,
+) {
+    let events = event_readers.read();
+
+    let mut leaving = lobby
+        .players()
+        .filter(|(player, _)| {
+            multi_input
+                .get(*player)
+                .is_some_and(|input| input.just_pressed(lobby.leave))
+        })
+        .collect::<Vec<_>>();
+    leaving.sort_unstable_by_key(|(player, _)| *player);
+    for (player, device) in leaving {
+        lobby.players.remove(&player);
+        multi_input.remove(player);
+        multi_scheme.remove(player);
+        lobby_events.send(LobbyEvent::Left { player, device });
+    }
+
+    let mut joining = Vec::new();
+    for event in &events.keyboard {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        let input = events.key(event.scan_code);
+        joining.extend(
+            (0..lobby.keyboard_templates.len())
+                .map(JoinDevice::Keyboard)
+                .find(|device| {
+                    lobby.player(*device).is_none() && lobby.is_join(*device, input)
+                }),
+        );
+    }
+    for event in &events.gamepad_buttons {
+        let device = JoinDevice::Gamepad(event.gamepad);
+        let button = GamepadButton::new(event.gamepad, event.button_type);
+        let input = UniversalInput::from(button).with_gamepad(PLAYER_GAMEPAD.id);
+        if event.value >= settings.get_button_settings(button).press_threshold()
+            && multi_input.free_gamepads().contains(&event.gamepad)
+            && lobby.is_join(device, input)
+        {
+            joining.push(device);
+        }
+    }
+
+    for device in joining {
+        if lobby.is_full(&multi_input) || lobby.player(device).is_some() {
+            continue;
+        }
+        let Some(template) = lobby.template(device).cloned() else {
+            continue;
+        };
+        let mut player = 0;
+        while multi_input.get(player).is_some() || multi_scheme.get(player).is_some() {
+            player += 1;
+        }
+        multi_scheme.insert(player, template);
+        multi_input.insert(player);
+        if let JoinDevice::Gamepad(gamepad) = device {
+            multi_input.assign_gamepad(player, gamepad);
+        }
+        lobby.players.insert(player, device);
+        lobby_events.send(LobbyEvent::Joined { player, device });
+    }
+}
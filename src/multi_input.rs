@@ -1,5 +1,8 @@
+use crate::action::Action;
 use crate::action_input::ActionInput;
+use crate::gamepad_slot::GamepadSlot;
 use bevy_ecs::system::Resource;
+use bevy_input::gamepad::Gamepad;
 use std::collections::HashMap;
 
 /// Helper function to be enable local multiplayer
@@ -24,9 +27,26 @@ use std::collections::HashMap;
 ///     }
 /// }
 /// ```
-#[derive(Debug, Clone, Resource, Default)]
+///
+/// Each player may own a `GamepadSlot`. Gamepads are assigned to players without one as
+/// they connect, and bindings made with `PLAYER_GAMEPAD` read the player's own gamepad.
+#[derive(Debug, Clone, Resource)]
 pub struct MultiInput {
     map: HashMap<usize, ActionInput>,
+    slots: HashMap<usize, GamepadSlot>,
+    free_gamepads: Vec<Gamepad>,
+    auto_assign: bool,
+}
+
+impl Default for MultiInput {
+    fn default() -> Self {
+        MultiInput {
+            map: HashMap::default(),
+            slots: HashMap::default(),
+            free_gamepads: Vec::default(),
+            auto_assign: true,
+        }
+    }
 }
 
 impl PartialEq for MultiInput {
@@ -53,8 +73,7 @@ impl PartialEq for MultiInput {
 
 impl MultiInput {
     pub fn new() -> Self {
-        let map = HashMap::new();
-        Self { map }
+        Self::default()
     }
 
     pub fn get(&self, id: usize) -> Option<&ActionInput> {
@@ -65,8 +84,16 @@ impl MultiInput {
         self.map.get_mut(&id)
     }
 
+    /// Adds a player, who is assigned a connected gamepad which no one owns yet.
     pub fn insert(&mut self, id: usize) {
         self.map.insert(id, ActionInput::default());
+        if self.auto_assign
+            && !self.slots.contains_key(&id)
+            && !self.free_gamepads.is_empty()
+        {
+            let gamepad = self.free_gamepads.remove(0);
+            self.assign_gamepad(id, gamepad);
+        }
     }
 
     pub fn has_players(&mut self, count: usize) {
@@ -77,11 +104,164 @@ impl MultiInput {
 
     pub fn remove(&mut self, id: usize) {
         self.map.remove(&id);
+        self.unassign_gamepad(id);
     }
 
     pub fn keys(&self) -> std::collections::hash_map::Keys<'_, usize, ActionInput> {
         self.map.keys()
     }
+
+    /// Whether any player is pressing `action`.
+    pub fn any_player_pressed<T>(&self, action: T) -> bool
+    where
+        T: Into<Action>,
+    {
+        let action = action.into();
+        self.map.values().any(|input| input.pressed(action))
+    }
+
+    /// Whether any player pressed `action` this frame.
+    pub fn any_player_just_pressed<T>(&self, action: T) -> bool
+    where
+        T: Into<Action>,
+    {
+        let action = action.into();
+        self.map.values().any(|input| input.just_pressed(action))
+    }
+
+    /// The ids of the players pressing `action`, from the lowest to the highest.
+    pub fn players_pressed<T>(&self, action: T) -> Vec<usize>
+    where
+        T: Into<Action>,
+    {
+        let action = action.into();
+        self.players_where(|input| input.pressed(action))
+    }
+
+    /// The ids of the players who pressed `action` this frame, from the lowest to the
+    /// highest.
+    pub fn players_just_pressed<T>(&self, action: T) -> Vec<usize>
+    where
+        T: Into<Action>,
+    {
+        let action = action.into();
+        self.players_where(|input| input.just_pressed(action))
+    }
+
+    fn players_where(&self, predicate: impl Fn(&ActionInput) -> bool) -> Vec<usize> {
+        let mut players = self
+            .map
+            .iter()
+            .filter(|(_, input)| predicate(input))
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+        players.sort_unstable();
+        players
+    }
+
+    /// The gamepad assigned to a player, which `PLAYER_GAMEPAD` bindings read.
+    pub fn gamepad(&self, id: usize) -> Option<Gamepad> {
+        self.slots.get(&id).map(|slot| slot.gamepad)
+    }
+
+    pub fn gamepad_slot(&self, id: usize) -> Option<&GamepadSlot> {
+        self.slots.get(&id)
+    }
+
+    /// The player a gamepad is assigned to.
+    pub fn player_with_gamepad(&self, gamepad: Gamepad) -> Option<usize> {
+        self.slots
+            .iter()
+            .find(|(_, slot)| slot.gamepad == gamepad)
+            .map(|(id, _)| *id)
+    }
+
+    /// Assigns a connected gamepad to a player, taking it from whoever owned it.
+    pub fn assign_gamepad(&mut self, id: usize, gamepad: Gamepad) {
+        if let Some(owner) = self.player_with_gamepad(gamepad) {
+            self.slots.remove(&owner);
+        }
+        self.free_gamepads.retain(|free| *free != gamepad);
+        self.unassign_gamepad(id);
+        self.slots.insert(
+            id,
+            GamepadSlot {
+                gamepad,
+                connected: true,
+            },
+        );
+    }
+
+    /// Takes a player's gamepad away. A connected gamepad can then be assigned to
+    /// another player.
+    pub fn unassign_gamepad(&mut self, id: usize) {
+        if let Some(slot) = self.slots.remove(&id) {
+            if slot.connected {
+                self.free_gamepads.push(slot.gamepad);
+            }
+        }
+    }
+
+    /// Connected gamepads which are not assigned to any player.
+    pub fn free_gamepads(&self) -> &[Gamepad] {
+        &self.free_gamepads
+    }
+
+    /// Whether connected gamepads are assigned to players without one. When disabled,
+    /// gamepads are only given back to players whose gamepad was disconnected, and
+    /// are otherwise assigned with `assign_gamepad`. Enabled by default.
+    pub fn set_auto_assign_gamepads(&mut self, auto_assign: bool) {
+        self.auto_assign = auto_assign;
+    }
+
+    pub fn auto_assign_gamepads(&self) -> bool {
+        self.auto_assign
+    }
+
+    /// Assigns a newly connected gamepad. A player whose gamepad was disconnected gets
+    /// it first, then a player without a gamepad, each by lowest id. If every player
+    /// has a gamepad, it is kept for the next player to be inserted.
+    pub fn connect_gamepad(&mut self, gamepad: Gamepad) {
+        self.connect(gamepad, self.auto_assign);
+    }
+
+    /// `connect_gamepad`, assigning gamepads to players without one only if
+    /// `auto_assign` is set.
+    pub(crate) fn connect(&mut self, gamepad: Gamepad, auto_assign: bool) {
+        if let Some(owner) = self.player_with_gamepad(gamepad) {
+            if let Some(slot) = self.slots.get_mut(&owner) {
+                slot.connected = true;
+            }
+            return;
+        }
+
+        let mut players = self.map.keys().copied().collect::<Vec<_>>();
+        players.sort_unstable();
+        let waiting = players
+            .iter()
+            .find(|id| self.slots.get(id).is_some_and(|slot| !slot.connected));
+        let without = players
+            .iter()
+            .find(|id| auto_assign && !self.slots.contains_key(id));
+        match waiting.or(without) {
+            Some(id) => self.assign_gamepad(*id, gamepad),
+            None if !self.free_gamepads.contains(&gamepad) => {
+                self.free_gamepads.push(gamepad);
+            }
+            None => {}
+        }
+    }
+
+    /// Marks a gamepad as disconnected. The player keeps the slot until a gamepad is
+    /// connected again.
+    pub fn disconnect_gamepad(&mut self, gamepad: Gamepad) {
+        self.free_gamepads.retain(|free| *free != gamepad);
+        if let Some(owner) = self.player_with_gamepad(gamepad) {
+            if let Some(slot) = self.slots.get_mut(&owner) {
+                slot.connected = false;
+            }
+        }
+    }
 }
 
 /// Eases the setup process for binding keys for multiplayer. The first argument is a
@@ -158,3 +338,56 @@ fn test_make_multi_input() {
     assert_eq!(mi, mi_t);
     assert_eq!(ms, ms_t);
 }
+
+#[test]
+fn test_gamepad_slots() {
+    let pad = Gamepad::new;
+    let mut mi = MultiInput::new();
+    mi.has_players(2);
+
+    mi.connect_gamepad(pad(3));
+    mi.connect_gamepad(pad(1));
+    assert_eq!(mi.gamepad(0), Some(pad(3)));
+    assert_eq!(mi.gamepad(1), Some(pad(1)));
+
+    // a reconnected gamepad goes back to the player waiting for it
+    mi.disconnect_gamepad(pad(3));
+    assert_eq!(
+        mi.gamepad_slot(0),
+        Some(&GamepadSlot {
+            gamepad: pad(3),
+            connected: false,
+        })
+    );
+    mi.connect_gamepad(pad(4));
+    assert_eq!(mi.gamepad(0), Some(pad(4)));
+
+    // spare gamepads wait for the next player
+    mi.connect_gamepad(pad(5));
+    assert_eq!(mi.player_with_gamepad(pad(5)), None);
+    mi.insert(2);
+    assert_eq!(mi.gamepad(2), Some(pad(5)));
+
+    mi.remove(1);
+    mi.insert(3);
+    assert_eq!(mi.gamepad(3), Some(pad(1)));
+}
+
+#[test]
+fn test_player_queries() {
+    let mut mi = MultiInput::new();
+    for id in [4, 1, 7] {
+        mi.insert(id);
+    }
+    mi.get_mut(7).unwrap().press("Pause");
+    mi.get_mut(1).unwrap().press("Pause");
+    mi.get_mut(4).unwrap().press("Jump");
+    mi.get_mut(4).unwrap().clear();
+
+    assert!(mi.any_player_pressed("Jump"));
+    assert!(!mi.any_player_just_pressed("Jump"));
+    assert!(mi.any_player_just_pressed("Pause"));
+    assert_eq!(mi.players_pressed("Pause"), vec![1, 7]);
+    assert_eq!(mi.players_just_pressed("Jump"), Vec::<usize>::new());
+    assert!(!mi.any_player_pressed("Crouch"));
+}
@@ -39,6 +39,14 @@ use std::collections::HashMap;
 /// }
 /// ```
 #[derive(PartialEq, Debug, Clone, Resource, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        from = "crate::serialization::MultiSchemeData",
+        into = "crate::serialization::MultiSchemeData"
+    )
+)]
 pub struct MultiScheme {
     map: HashMap<usize, ControlScheme>,
 }
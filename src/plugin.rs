@@ -1,7 +1,21 @@
-use bevy_app::{App, Plugin, PreUpdate};
+use std::marker::PhantomData;
+
+use bevy_app::{App, Plugin, PreStartup, PreUpdate};
+use bevy_ecs::schedule::common_conditions::{resource_changed, resource_exists};
 use bevy_ecs::schedule::{IntoSystemConfigs, IntoSystemSetConfigs, SystemSet};
+use bevy_ecs::system::Resource;
 
+use crate::action::{Action, ActionType};
 use crate::action_input::ActionInput;
+use crate::context::{update_action_contexts, ActionContexts, ContextEvent};
+use crate::gamepad_slot::assign_gamepads;
+use crate::lobby::{lobby_system, Lobby, LobbyEvent};
+use crate::rebind::{typed_rebind_system, RebindEvent, Rebinding};
+use crate::registry::{
+    warn_unregistered_actions, warn_unregistered_player_actions, ActionRegistry,
+    RegistryWarnings,
+};
+use crate::scan_code::{apply_scan_code_table, ActiveScanCodeTable};
 use crate::{
     control_scheme::ControlScheme, multi_input::MultiInput, multi_scheme::MultiScheme,
 };
@@ -15,7 +29,7 @@ use crate::{
 ///
 /// fn main() {
 ///    App::new()
-///        .add_plugins(ActionMapPlugin)
+///        .add_plugins(ActionMapPlugin::new())
 ///        .add_systems(
 ///            PreUpdate,
 ///            handle_input.in_set(ActionMapSet::HandleActions),
@@ -32,46 +46,224 @@ pub enum ActionMapSet {
     HandleActions,
 }
 
-pub struct ActionMapPlugin;
+/// Reads input events into the `ActionInput` resource according to the `ControlScheme`
+/// resource.
+/// ```rust
+/// use bevy::prelude::*;
+/// use action_maps::prelude::*;
+///
+/// App::new().add_plugins(ActionMapPlugin::new());
+/// ```
+///
+/// The plugin can also be created for a typed action enum, which then replaces `Action`
+/// in the `ActionInput`, `ControlScheme` and `Rebinding` resources.
+/// ```rust
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use bevy::prelude::*;
+/// use action_maps::prelude::*;
+///
+/// #[derive(Actionlike, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// enum Actions {
+///     Jump,
+/// }
+///
+/// App::new().add_plugins(ActionMapPlugin::<Actions>::default());
+///
+/// fn handle_input(input: Res<ActionInput<Actions>>) {
+///     if input.just_pressed(Actions::Jump) {
+///         println!("Jump!");
+///     }
+/// }
+/// # }
+/// ```
+pub struct ActionMapPlugin<A: ActionType = Action> {
+    #[cfg(feature = "asset")]
+    control_scheme_asset: Option<String>,
+    marker: PhantomData<fn() -> A>,
+}
+
+impl<A: ActionType> Default for ActionMapPlugin<A> {
+    fn default() -> Self {
+        ActionMapPlugin {
+            #[cfg(feature = "asset")]
+            control_scheme_asset: None,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl ActionMapPlugin {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the `ControlScheme` resource from a `.controls.ron` asset. The asset is
+    /// applied again whenever it is modified, so bindings can be tweaked while the game
+    /// is running when Bevy's asset hot reloading is enabled.
+    ///
+    /// Applying the asset replaces the whole `ControlScheme`, so every reload discards
+    /// the bindings changed at runtime, such as those made with `Rebinding`. Save player
+    /// rebinds separately and apply them again after a reload.
+    /// ```rust,no_run
+    /// use bevy::prelude::*;
+    /// use action_maps::prelude::*;
+    ///
+    /// App::new()
+    ///     .add_plugins(DefaultPlugins)
+    ///     .add_plugins(
+    ///         ActionMapPlugin::new()
+    ///             .with_control_scheme_asset("controls/default.controls.ron"),
+    ///     )
+    ///     .run();
+    /// ```
+    #[cfg(feature = "asset")]
+    pub fn with_control_scheme_asset<P>(mut self, path: P) -> Self
+    where
+        P: Into<String>,
+    {
+        self.control_scheme_asset = Some(path.into());
+        self
+    }
+}
+
+impl<A: ActionType> Plugin for ActionMapPlugin<A> {
+    fn build(&self, app: &mut App) {
+        #[cfg(feature = "asset")]
+        if let Some(path) = &self.control_scheme_asset {
+            crate::asset::build(app, path);
+        }
+
+        add_once(app, ActionMapCorePlugin);
+        add_once(app, EntityInputPlugin::<A>(PhantomData));
+
+        insert_once(app, ControlScheme::<A>::empty);
+        insert_once(app, ActionInput::<A>::empty);
+        insert_once(app, Rebinding::<A>::idle);
+
+        app.init_resource::<ActionContexts<A>>()
+            .add_event::<RebindEvent<A>>()
+            .add_event::<ContextEvent<A>>()
+            .add_systems(
+                PreUpdate,
+                (
+                    update_action_contexts::<A>,
+                    typed_rebind_system::<A>,
+                    crate::input::typed_universal_input_system::<A>,
+                )
+                    .chain()
+                    .in_set(ActionMapSet::ReadEvents),
+            );
+    }
+}
+
+/// Reads input events into the `ActionInput` of each player in the `MultiInput`
+/// resource according to the `MultiScheme` resource.
+///
+/// It can be used together with `ActionMapPlugin`, whose `ControlScheme` and
+/// `ActionInput` resources then hold actions which belong to no player in particular.
+/// ```rust
+/// use bevy::prelude::*;
+/// use action_maps::multiplayer_prelude::*;
+///
+/// App::new()
+///     .add_plugins((ActionMapPlugin::new(), MultiActionMapPlugin))
+///     .insert_resource(make_controls!(("Pause", KeyCode::Escape)));
+/// ```
+pub struct MultiActionMapPlugin;
 
-impl Plugin for ActionMapPlugin {
+impl Plugin for MultiActionMapPlugin {
+    fn build(&self, app: &mut App) {
+        add_once(app, ActionMapCorePlugin);
+        add_once(app, EntityInputPlugin::<Action>(PhantomData));
+
+        app.init_resource::<MultiScheme>()
+            .init_resource::<MultiInput>()
+            .add_event::<LobbyEvent>()
+            .add_systems(
+                PreUpdate,
+                warn_unregistered_player_actions.before(ActionMapSet::ReadEvents),
+            )
+            .add_systems(
+                PreUpdate,
+                (
+                    assign_gamepads,
+                    crate::input::multi_universal_input_system,
+                    lobby_system.run_if(resource_exists::<Lobby>()),
+                )
+                    .chain()
+                    .in_set(ActionMapSet::ReadEvents),
+            );
+    }
+}
+
+/// Inserts a resource unless the app already has one, like `App::init_resource` for
+/// resources which only implement `Default` for `Action`.
+fn insert_once<R: Resource>(app: &mut App, resource: impl FnOnce() -> R) {
+    if !app.world.contains_resource::<R>() {
+        app.insert_resource(resource());
+    }
+}
+
+/// Adds a plugin unless another plugin already has.
+fn add_once<P: Plugin>(app: &mut App, plugin: P) {
+    if !app.is_plugin_added::<P>() {
+        app.add_plugins(plugin);
+    }
+}
+
+/// The setup shared by `ActionMapPlugin` and `MultiActionMapPlugin`, so the two can be
+/// added to the same app.
+struct ActionMapCorePlugin;
+
+impl Plugin for ActionMapCorePlugin {
     fn build(&self, app: &mut App) {
         app.configure_sets(
             PreUpdate,
             ActionMapSet::HandleActions.after(ActionMapSet::ReadEvents),
         )
-        .init_resource::<ControlScheme>()
-        .init_resource::<ActionInput>()
+        .init_resource::<ActionRegistry>()
+        .init_resource::<RegistryWarnings>()
         .init_resource::<bevy_input::gamepad::GamepadSettings>()
+        .init_resource::<bevy_time::Time>()
+        .init_resource::<ActiveScanCodeTable>()
         .add_event::<bevy_input::keyboard::KeyboardInput>()
         .add_event::<bevy_input::gamepad::GamepadButtonChangedEvent>()
+        .add_event::<bevy_input::gamepad::GamepadAxisChangedEvent>()
         .add_event::<bevy_input::gamepad::GamepadButtonInput>()
+        .add_event::<bevy_input::gamepad::GamepadConnectionEvent>()
         .add_event::<bevy_input::mouse::MouseButtonInput>()
+        .add_event::<bevy_input::mouse::MouseMotion>()
+        .add_event::<bevy_input::mouse::MouseWheel>()
+        .add_systems(PreStartup, apply_scan_code_table)
         .add_systems(
             PreUpdate,
-            (crate::input::universal_input_system).in_set(ActionMapSet::ReadEvents),
+            crate::input::gamepad_button_input_system.in_set(ActionMapSet::ReadEvents),
+        )
+        .add_systems(
+            PreUpdate,
+            apply_scan_code_table
+                .run_if(resource_changed::<ActiveScanCodeTable>())
+                .before(ActionMapSet::ReadEvents),
         );
     }
 }
 
-pub struct MultiActionMapPlugin;
+/// Updates the `ActionInput` components of one action type, and checks the actions of that
+/// type against the `ActionRegistry`, however many plugins use it.
+struct EntityInputPlugin<A: ActionType>(PhantomData<fn() -> A>);
 
-impl Plugin for MultiActionMapPlugin {
+impl<A: ActionType> Plugin for EntityInputPlugin<A> {
     fn build(&self, app: &mut App) {
-        app.configure_sets(
+        app.add_systems(
             PreUpdate,
-            ActionMapSet::HandleActions.after(ActionMapSet::ReadEvents),
+            warn_unregistered_actions::<A>.before(ActionMapSet::ReadEvents),
         )
-        .init_resource::<MultiScheme>()
-        .init_resource::<MultiInput>()
-        .init_resource::<bevy_input::gamepad::GamepadSettings>()
-        .add_event::<bevy_input::keyboard::KeyboardInput>()
-        .add_event::<bevy_input::gamepad::GamepadButtonChangedEvent>()
-        .add_event::<bevy_input::gamepad::GamepadButtonInput>()
-        .add_event::<bevy_input::mouse::MouseButtonInput>()
         .add_systems(
             PreUpdate,
-            (crate::input::multi_universal_input_system)
+            crate::input::entity_input_system::<A>
+                .after(update_action_contexts::<A>)
+                .after(typed_rebind_system::<A>)
                 .in_set(ActionMapSet::ReadEvents),
         );
     }
@@ -0,0 +1,298 @@
+use bevy_ecs::event::{Event, EventWriter};
+use bevy_ecs::system::{Res, ResMut, Resource};
+use bevy_input::gamepad::{GamepadAxis, GamepadButton, GamepadSettings};
+use bevy_input::ButtonState;
+use bevy_time::Time;
+use std::collections::HashSet;
+use std::time::Duration;
+
+use crate::action::{Action, ActionType};
+use crate::control_scheme::ControlScheme;
+use crate::input::{InputEventReaders, InputEvents, InputKind, UniversalInput};
+use crate::registry::ActionRegistry;
+
+/// How far a gamepad axis has to move before it is captured.
+const AXIS_CAPTURE_THRESHOLD: f32 = 0.5;
+
+/// Describes which action to rebind and which inputs may be bound to it.
+/// ```rust
+/// use std::time::Duration;
+/// use bevy::prelude::*;
+/// use action_maps::prelude::*;
+/// use action_maps::input::InputKind;
+///
+/// fn start_rebinding(mut rebinding: ResMut<Rebinding>) {
+///     rebinding.start(
+///         RebindRequest::new("Jump")
+///             .with_kind(InputKind::Keyboard)
+///             .with_timeout(Duration::from_secs(5)),
+///     );
+/// }
+///
+/// fn read_rebinds(mut events: EventReader<RebindEvent>) {
+///     for event in events.read() {
+///         if let RebindEvent::Bound { action, input, .. } = event {
+///             println!("{} is now bound to {:?}", action.name(), input);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RebindRequest<A: ActionType = Action> {
+    action: A,
+    kinds: Vec<InputKind>,
+    timeout: Option<Duration>,
+    cancel: UniversalInput,
+}
+
+impl<A: ActionType> RebindRequest<A> {
+    pub fn new<T>(action: T) -> Self
+    where
+        T: Into<A>,
+    {
+        RebindRequest {
+            action: action.into(),
+            kinds: Vec::new(),
+            timeout: None,
+            cancel: UniversalInput::Escape,
+        }
+    }
+
+    /// Only captures inputs of the given kind. May be called several times to allow
+    /// several kinds. When no kind is given, inputs of every kind are captured.
+    pub fn with_kind(mut self, kind: InputKind) -> Self {
+        if !self.kinds.contains(&kind) {
+            self.kinds.push(kind);
+        }
+        self
+    }
+
+    /// Stops capturing once `timeout` has passed without a qualifying input.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the input which cancels the capture. Defaults to `Escape`.
+    pub fn with_cancel<I>(mut self, input: I) -> Self
+    where
+        I: Into<UniversalInput>,
+    {
+        self.cancel = input.into();
+        self
+    }
+
+    pub fn action(&self) -> A {
+        self.action
+    }
+
+    fn accepts(&self, input: UniversalInput) -> bool {
+        self.kinds.is_empty() || self.kinds.contains(&input.kind())
+    }
+}
+
+/// Puts the `ActionMapPlugin` into capture mode. While capturing, the next qualifying
+/// input is bound to the requested action in the `ControlScheme`, replacing the
+/// action's previous bindings of the same kind, and a `RebindEvent` is sent. Actions
+/// are not dispatched while capturing, so the captured input does not also fire
+/// gameplay.
+#[derive(Resource, Debug)]
+pub struct Rebinding<A: ActionType = Action> {
+    request: Option<RebindRequest<A>>,
+    started_at: Option<Duration>,
+    finished_this_frame: bool,
+    held_keys: HashSet<u32>,
+}
+
+impl Default for Rebinding {
+    fn default() -> Self {
+        Self::idle()
+    }
+}
+
+impl<A: ActionType> Rebinding<A> {
+    /// A `Rebinding` which isn't capturing, for any action type.
+    pub fn idle() -> Self {
+        Rebinding {
+            request: None,
+            started_at: None,
+            finished_this_frame: false,
+            held_keys: HashSet::default(),
+        }
+    }
+
+    pub fn start(&mut self, request: RebindRequest<A>) {
+        self.request = Some(request);
+        self.started_at = None;
+    }
+
+    /// Stops capturing without sending a `RebindEvent`.
+    pub fn cancel(&mut self) {
+        self.request = None;
+        self.started_at = None;
+    }
+
+    pub fn is_capturing(&self) -> bool {
+        self.request.is_some()
+    }
+
+    pub fn request(&self) -> Option<&RebindRequest<A>> {
+        self.request.as_ref()
+    }
+
+    /// Whether action dispatch should be suspended this frame.
+    pub(crate) fn is_suspending(&self) -> bool {
+        self.is_capturing() || self.finished_this_frame
+    }
+}
+
+#[derive(Event, Debug, Clone, PartialEq)]
+pub enum RebindEvent<A: ActionType = Action> {
+    /// The input was bound to the action. `displaced` is the action the input was
+    /// bound to before, which is now unbound from it.
+    Bound {
+        action: A,
+        input: UniversalInput,
+        displaced: Option<A>,
+    },
+    /// The capture was cancelled with the cancel input, or the action is not
+    /// rebindable.
+    Cancelled { action: A },
+    /// No qualifying input was pressed before the timeout.
+    TimedOut { action: A },
+}
+
+pub fn rebind_system(
+    event_readers: InputEventReaders,
+    rebinding: ResMut<Rebinding>,
+    control_scheme: ResMut<ControlScheme>,
+    rebind_events: EventWriter<RebindEvent>,
+    settings: Res<GamepadSettings>,
+    time: Res<Time>,
+    registry: Option<Res<ActionRegistry>>,
+) {
+    typed_rebind_system(
+        event_readers,
+        rebinding,
+        control_scheme,
+        rebind_events,
+        settings,
+        time,
+        registry,
+    );
+}
+
+/// `rebind_system` for the `Rebinding` and `ControlScheme` of any action type.
+pub fn typed_rebind_system<A: ActionType>(
+    mut event_readers: InputEventReaders,
+    mut rebinding: ResMut<Rebinding<A>>,
+    mut control_scheme: ResMut<ControlScheme<A>>,
+    mut rebind_events: EventWriter<RebindEvent<A>>,
+    settings: Res<GamepadSettings>,
+    time: Res<Time>,
+    registry: Option<Res<ActionRegistry>>,
+) {
+    let events = event_readers.read();
+    rebinding.finished_this_frame = false;
+    // held keys are tracked while idle too, so the OS repeats of a key held when the
+    // capture starts, such as the one which opened the rebind menu, aren't captured
+    let pressed = pressed_inputs(&events, &settings, &mut rebinding.held_keys);
+    let Some(request) = rebinding.request.clone() else {
+        return;
+    };
+    let now = time.elapsed();
+    let started_at = *rebinding.started_at.get_or_insert(now);
+    let action = request.action;
+
+    let rebindable = registry
+        .map(|registry| registry.is_rebindable(Action::new(action.action_name())))
+        .unwrap_or(true);
+    let event = if !rebindable || pressed.contains(&request.cancel) {
+        RebindEvent::Cancelled { action }
+    } else if let Some(input) = pressed.into_iter().find(|i| request.accepts(*i)) {
+        let replaced = control_scheme
+            .iter()
+            .filter(|(bound, bound_action)| {
+                **bound_action == action && bound.kind() == input.kind()
+            })
+            .map(|(bound, _)| *bound)
+            .collect::<Vec<_>>();
+        for bound in replaced {
+            control_scheme.remove(bound);
+        }
+        let displaced = control_scheme.insert(action, input);
+        RebindEvent::Bound {
+            action,
+            input,
+            displaced,
+        }
+    } else if request
+        .timeout
+        .is_some_and(|timeout| now - started_at >= timeout)
+    {
+        RebindEvent::TimedOut { action }
+    } else {
+        return;
+    };
+
+    rebinding.cancel();
+    rebinding.finished_this_frame = true;
+    rebind_events.send(event);
+}
+
+/// Every input which was pressed this frame, in the order of the event types. Keys in
+/// `held_keys` are repeats rather than new presses, and `held_keys` is updated with the
+/// keyboard events of this frame.
+fn pressed_inputs(
+    events: &InputEvents,
+    settings: &GamepadSettings,
+    held_keys: &mut HashSet<u32>,
+) -> Vec<UniversalInput> {
+    let mut pressed = Vec::new();
+
+    for event in &events.keyboard {
+        match event.state {
+            ButtonState::Pressed => {
+                if held_keys.insert(event.scan_code) {
+                    pressed.push(events.key(event.scan_code));
+                }
+            }
+            ButtonState::Released => {
+                held_keys.remove(&event.scan_code);
+            }
+        }
+    }
+
+    for event in &events.mouse_buttons {
+        if event.state == ButtonState::Pressed {
+            pressed.push(event.button.into());
+        }
+    }
+
+    for event in &events.mouse_wheel {
+        if event.y > 0. {
+            pressed.push(UniversalInput::MouseWheelUp);
+        } else if event.y < 0. {
+            pressed.push(UniversalInput::MouseWheelDown);
+        } else if event.x < 0. {
+            pressed.push(UniversalInput::MouseWheelLeft);
+        } else if event.x > 0. {
+            pressed.push(UniversalInput::MouseWheelRight);
+        }
+    }
+
+    for event in &events.gamepad_buttons {
+        let button = GamepadButton::new(event.gamepad, event.button_type);
+        if event.value >= settings.get_button_settings(button).press_threshold() {
+            pressed.push(button.into());
+        }
+    }
+
+    for event in &events.gamepad_axes {
+        if event.value.abs() >= AXIS_CAPTURE_THRESHOLD {
+            pressed.push(GamepadAxis::new(event.gamepad, event.axis_type).into());
+        }
+    }
+
+    pressed
+}
@@ -0,0 +1,505 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::RwLock;
+
+use bevy_ecs::change_detection::{DetectChanges, DetectChangesMut};
+use bevy_ecs::system::{Query, Res, ResMut, Resource};
+use bevy_ecs::world::Ref;
+use bevy_log::warn;
+
+use crate::action::{Action, ActionType};
+use crate::action_input::ActionInput;
+use crate::control_scheme::ControlScheme;
+use crate::multi_input::MultiInput;
+use crate::multi_scheme::MultiScheme;
+
+/// How a registered action is presented to players, for example on a rebinding screen.
+/// Actions are rebindable unless marked otherwise.
+/// ```rust
+/// use action_maps::actions::ActionMetadata;
+///
+/// let jump = ActionMetadata::new()
+///     .with_display_name("Jump")
+///     .with_localization_key("actions.jump")
+///     .with_description("Makes your character jump")
+///     .with_category("Movement")
+///     .with_sort_order(1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActionMetadata {
+    pub display_name: Option<String>,
+    /// The key of the display name in the game's localization files.
+    pub localization_key: Option<String>,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    /// Orders actions within their category, lowest first.
+    pub sort_order: i32,
+    /// Whether players may rebind the action. Rebinding an action which is not
+    /// rebindable is cancelled.
+    pub rebindable: bool,
+}
+
+impl Default for ActionMetadata {
+    fn default() -> Self {
+        ActionMetadata {
+            display_name: None,
+            localization_key: None,
+            description: None,
+            category: None,
+            sort_order: 0,
+            rebindable: true,
+        }
+    }
+}
+
+impl ActionMetadata {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_display_name<S>(mut self, display_name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.display_name = Some(display_name.into());
+        self
+    }
+
+    pub fn with_localization_key<S>(mut self, localization_key: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.localization_key = Some(localization_key.into());
+        self
+    }
+
+    pub fn with_description<S>(mut self, description: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn with_category<S>(mut self, category: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.category = Some(category.into());
+        self
+    }
+
+    pub fn with_sort_order(mut self, sort_order: i32) -> Self {
+        self.sort_order = sort_order;
+        self
+    }
+
+    pub fn with_rebindable(mut self, rebindable: bool) -> Self {
+        self.rebindable = rebindable;
+        self
+    }
+}
+
+/// The actions known to the game. Once an action is registered, debug builds warn
+/// about actions which are bound in the `ControlScheme` or queried from the
+/// `ActionInput` without being registered, which usually means a misspelled name. The
+/// plugins check each app against its own registry before input events are read, so a
+/// query is reported on the frame after it is made.
+/// ```rust
+/// use bevy::prelude::*;
+/// use action_maps::prelude::*;
+/// use action_maps::actions::ActionMetadata;
+///
+/// fn register_actions(mut registry: ResMut<ActionRegistry>) {
+///     registry.register("Jump");
+///     registry.register_with(
+///         "Shoot",
+///         ActionMetadata::new().with_display_name("Fire").with_category("Combat"),
+///     );
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ActionRegistry {
+    order: Vec<Action>,
+    actions: HashMap<Action, ActionMetadata>,
+}
+
+impl ActionRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register<T>(&mut self, action: T)
+    where
+        T: Into<Action>,
+    {
+        self.register_with(action, ActionMetadata::default());
+    }
+
+    /// Registers an action with its `ActionMetadata`, replacing the metadata of an
+    /// action which is already registered.
+    pub fn register_with<T>(&mut self, action: T, metadata: ActionMetadata)
+    where
+        T: Into<Action>,
+    {
+        let action = action.into();
+        if self.actions.insert(action, metadata).is_none() {
+            self.order.push(action);
+        }
+    }
+
+    pub fn contains<T>(&self, action: T) -> bool
+    where
+        T: Into<Action>,
+    {
+        self.actions.contains_key(&action.into())
+    }
+
+    pub fn metadata<T>(&self, action: T) -> Option<&ActionMetadata>
+    where
+        T: Into<Action>,
+    {
+        self.actions.get(&action.into())
+    }
+
+    /// The display name of an action, or its name if it has none.
+    pub fn display_name<T>(&self, action: T) -> &str
+    where
+        T: Into<Action>,
+    {
+        let action = action.into();
+        self.metadata(action)
+            .and_then(|metadata| metadata.display_name.as_deref())
+            .unwrap_or(action.name())
+    }
+
+    /// Whether players may rebind the action. Unregistered actions are rebindable.
+    pub fn is_rebindable<T>(&self, action: T) -> bool
+    where
+        T: Into<Action>,
+    {
+        self.metadata(action)
+            .map(|metadata| metadata.rebindable)
+            .unwrap_or(true)
+    }
+
+    /// Iterates over the registered actions in the order they were registered.
+    pub fn iter(&self) -> impl Iterator<Item = (Action, &ActionMetadata)> {
+        self.order
+            .iter()
+            .map(|action| (*action, &self.actions[action]))
+    }
+
+    /// The registered actions in the order a settings menu lists them: grouped by
+    /// category in the order the categories were first registered, then by sort
+    /// order, then in the order they were registered.
+    pub fn sorted(&self) -> Vec<(Action, &ActionMetadata)> {
+        let mut categories = Vec::<Option<&str>>::new();
+        for (_, metadata) in self.iter() {
+            if !categories.contains(&metadata.category.as_deref()) {
+                categories.push(metadata.category.as_deref());
+            }
+        }
+
+        let mut actions = self.iter().collect::<Vec<_>>();
+        actions.sort_by_key(|(_, metadata)| {
+            let category = categories
+                .iter()
+                .position(|category| *category == metadata.category.as_deref());
+            (category, metadata.sort_order)
+        });
+        actions
+    }
+
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    /// Lists the registered actions which have no binding at all in `controls`, in the
+    /// order they were registered.
+    pub fn unbound<A: ActionType>(&self, controls: &ControlScheme<A>) -> Vec<Action> {
+        let bound = bound_names(controls);
+        self.order
+            .iter()
+            .filter(|action| !bound.contains(action.name()))
+            .copied()
+            .collect()
+    }
+}
+
+fn bound_names<A: ActionType>(controls: &ControlScheme<A>) -> HashSet<&'static str> {
+    controls
+        .iter()
+        .map(|(_, action)| action)
+        .chain(controls.iter_chords().map(|(_, action)| action))
+        .chain(
+            controls
+                .iter_interactions()
+                .flat_map(|(_, bindings)| bindings.iter().map(|(_, action)| action)),
+        )
+        .chain(controls.iter_dual_axes().map(|(action, _)| action))
+        .chain(
+            controls.iter_shared().flat_map(|(_, bindings)| {
+                bindings.iter().map(|binding| &binding.action)
+            }),
+        )
+        .map(ActionType::action_name)
+        .collect()
+}
+
+/// The actions an `ActionInput` was queried for. Debug builds record them so the plugins
+/// can check them against the `ActionRegistry`.
+pub(crate) struct QueriedActions<A: ActionType>(RwLock<HashSet<A>>);
+
+impl<A: ActionType> QueriedActions<A> {
+    pub(crate) fn record(&self, action: A) {
+        if !cfg!(debug_assertions) {
+            return;
+        }
+        if self
+            .0
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains(&action)
+        {
+            return;
+        }
+        self.0
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(action);
+    }
+
+    fn names(&self) -> Vec<&'static str> {
+        self.0
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(ActionType::action_name)
+            .collect()
+    }
+}
+
+impl<A: ActionType> Default for QueriedActions<A> {
+    fn default() -> Self {
+        QueriedActions(RwLock::new(HashSet::new()))
+    }
+}
+
+impl<A: ActionType> Clone for QueriedActions<A> {
+    fn clone(&self) -> Self {
+        let queried = self.0.read().unwrap_or_else(|e| e.into_inner()).clone();
+        QueriedActions(RwLock::new(queried))
+    }
+}
+
+impl<A: ActionType> std::fmt::Debug for QueriedActions<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("QueriedActions").field(&self.0).finish()
+    }
+}
+
+/// The unregistered actions which were already warned about, so each action is only
+/// reported once per app.
+#[derive(Resource, Debug, Default)]
+pub(crate) struct RegistryWarnings {
+    warned: HashSet<&'static str>,
+}
+
+impl RegistryWarnings {
+    fn check(&mut self, registry: &ActionRegistry, name: &'static str, usage: &str) {
+        if !self.warned.contains(name) && !registry.contains(Action::new(name)) {
+            self.warned.insert(name);
+            warn!("Action {name} is {usage} but is not in the ActionRegistry");
+        }
+    }
+
+    fn check_bindings<A: ActionType>(
+        &mut self,
+        registry: &ActionRegistry,
+        controls: &ControlScheme<A>,
+    ) {
+        for name in bound_names(controls) {
+            self.check(registry, name, "bound");
+        }
+    }
+
+    fn check_queries<A: ActionType>(
+        &mut self,
+        registry: &ActionRegistry,
+        action_input: &ActionInput<A>,
+    ) {
+        for name in action_input.queried().names() {
+            self.check(registry, name, "queried");
+        }
+    }
+}
+
+/// Warns about the actions of one action type which are bound in a `ControlScheme` or
+/// queried from an `ActionInput` without being registered, both for the resources and
+/// the components. Queries are reported on the frame after they are made. Does nothing
+/// in release builds or while the registry is empty.
+pub(crate) fn warn_unregistered_actions<A: ActionType>(
+    registry: Res<ActionRegistry>,
+    mut warnings: ResMut<RegistryWarnings>,
+    control_scheme: Option<Res<ControlScheme<A>>>,
+    action_input: Option<Res<ActionInput<A>>>,
+    control_schemes: Query<Ref<ControlScheme<A>>>,
+    action_inputs: Query<&ActionInput<A>>,
+) {
+    if !cfg!(debug_assertions) || registry.is_empty() {
+        return;
+    }
+    let warnings = warnings.bypass_change_detection();
+
+    let control_schemes = control_scheme
+        .as_ref()
+        .map(|controls| (controls.is_changed(), &**controls))
+        .into_iter()
+        .chain(
+            control_schemes
+                .iter()
+                .map(|controls| (controls.is_changed(), controls.into_inner())),
+        );
+    for (changed, controls) in control_schemes {
+        if changed || registry.is_changed() {
+            warnings.check_bindings(&registry, controls);
+        }
+    }
+
+    for action_input in action_input.as_deref().into_iter().chain(&action_inputs) {
+        warnings.check_queries(&registry, action_input);
+    }
+}
+
+/// `warn_unregistered_actions` for the players of the `MultiScheme` and `MultiInput`.
+pub(crate) fn warn_unregistered_player_actions(
+    registry: Res<ActionRegistry>,
+    mut warnings: ResMut<RegistryWarnings>,
+    multi_scheme: Res<MultiScheme>,
+    multi_input: Res<MultiInput>,
+) {
+    if !cfg!(debug_assertions) || registry.is_empty() {
+        return;
+    }
+    let warnings = warnings.bypass_change_detection();
+
+    if multi_scheme.is_changed() || registry.is_changed() {
+        for controls in multi_scheme.keys().filter_map(|id| multi_scheme.get(*id)) {
+            warnings.check_bindings(&registry, controls);
+        }
+    }
+    for action_input in multi_input.keys().filter_map(|id| multi_input.get(*id)) {
+        warnings.check_queries(&registry, action_input);
+    }
+}
+
+#[test]
+fn test_unbound_actions() {
+    use crate::chord::Chord;
+    use bevy::prelude::ScanCode;
+
+    let mut registry = ActionRegistry::new();
+    registry.register("Jump");
+    registry.register("Save");
+    registry.register_with("Crouch", ActionMetadata::new().with_category("Movement"));
+
+    let mut controls = ControlScheme::new();
+    controls.insert("Jump", ScanCode(0x39));
+    controls.insert_chord("Save", Chord::new([ScanCode(0x1D)], ScanCode(0x1F)));
+
+    assert_eq!(registry.unbound(&controls), vec![Action::from("Crouch")]);
+    assert_eq!(
+        registry
+            .metadata("Crouch")
+            .and_then(|metadata| metadata.category.as_deref()),
+        Some("Movement")
+    );
+}
+
+#[test]
+fn test_sorted_actions() {
+    let mut registry = ActionRegistry::new();
+    let movement = || ActionMetadata::new().with_category("Movement");
+    registry.register_with("Jump", movement().with_sort_order(1));
+    registry.register_with("Shoot", ActionMetadata::new().with_category("Combat"));
+    registry.register_with("Walk", movement());
+    registry.register_with(
+        "Pause",
+        ActionMetadata::new()
+            .with_display_name("Pause Game")
+            .with_rebindable(false),
+    );
+
+    let sorted = registry
+        .sorted()
+        .into_iter()
+        .map(|(action, _)| registry.display_name(action))
+        .collect::<Vec<_>>();
+    assert_eq!(sorted, vec!["Walk", "Jump", "Shoot", "Pause Game"]);
+    assert!(!registry.is_rebindable("Pause"));
+    assert!(registry.is_rebindable("Jump"));
+    assert!(registry.is_rebindable("Crouch"));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+fn test_warnings_are_per_app() {
+    use crate::prelude::*;
+    use bevy::prelude::{App, ScanCode};
+
+    let mut registered = App::new();
+    registered.add_plugins(ActionMapPlugin::new());
+    registered
+        .world
+        .resource_mut::<ActionRegistry>()
+        .register("Jump");
+    registered.insert_resource(make_controls!(("Jmup", ScanCode(0x39))));
+    let mut unregistered = App::new();
+    unregistered.add_plugins(ActionMapPlugin::new());
+    unregistered.insert_resource(make_controls!(("Jmup", ScanCode(0x39))));
+    registered.update();
+    unregistered.update();
+
+    registered.world.resource::<ActionInput>().pressed("Crouch");
+    unregistered
+        .world
+        .resource::<ActionInput>()
+        .pressed("Crouch");
+    registered.update();
+    unregistered.update();
+
+    let warned = |app: &App| app.world.resource::<RegistryWarnings>().warned.clone();
+    assert_eq!(warned(&registered), HashSet::from(["Jmup", "Crouch"]));
+    assert!(warned(&unregistered).is_empty());
+}
+
+#[test]
+#[cfg(debug_assertions)]
+fn test_every_query_is_checked() {
+    use crate::prelude::*;
+    use bevy::prelude::App;
+
+    let mut app = App::new();
+    app.add_plugins(ActionMapPlugin::new());
+    app.world.resource_mut::<ActionRegistry>().register("Jump");
+    app.update();
+
+    let input = app.world.resource::<ActionInput>();
+    input.axis("Typo");
+    input.dual_axis("Mvoe");
+    input.just_released("Jmup");
+    input.just_triggered("Dahs");
+    input.any_pressed(["Jump", "Crouhc"]);
+    input.any_just_pressed(["Shoto"]);
+    app.update();
+
+    let warned = app.world.resource::<RegistryWarnings>().warned.clone();
+    assert_eq!(
+        warned,
+        HashSet::from(["Typo", "Mvoe", "Jmup", "Dahs", "Crouhc", "Shoto"])
+    );
+}
@@ -0,0 +1,754 @@
+use bevy_ecs::system::{Res, Resource};
+use bevy_input::keyboard::{KeyCode, ScanCode};
+use bevy_log::warn;
+use bevy_reflect::Enum;
+use std::sync::{Arc, RwLock};
+
+use crate::input::UniversalInput;
+use crate::KeyError;
+
+/// Converts between key names and scan codes. Key names are the names of the
+/// `UniversalInput` and `KeyCode` variants, along with any aliases the table supports.
+/// A custom table can be used for keyboards the built-in tables do not describe.
+/// ```rust
+/// use bevy::prelude::*;
+/// use action_maps::input::{ActiveScanCodeTable, ScanCodeTable, UniversalInput};
+/// use action_maps::KeyError;
+///
+/// struct NumpadOnly;
+///
+/// impl ScanCodeTable for NumpadOnly {
+///     fn scan_code(&self, key: &str) -> Result<u32, KeyError> {
+///         match key {
+///             "NumpadEnter" => Ok(0x60),
+///             key => Err(KeyError::KeyNotFound(key.to_string())),
+///         }
+///     }
+///
+///     fn key(&self, scan_code: u32) -> Result<UniversalInput, KeyError> {
+///         match scan_code {
+///             0x60 => Ok(UniversalInput::NumpadEnter),
+///             sc => Err(KeyError::ScanCodeNotFound(sc.to_string())),
+///         }
+///     }
+/// }
+///
+/// App::new().insert_resource(ActiveScanCodeTable::new(NumpadOnly));
+/// ```
+pub trait ScanCodeTable: Send + Sync + 'static {
+    fn scan_code(&self, key: &str) -> Result<u32, KeyError>;
+
+    fn key(&self, scan_code: u32) -> Result<UniversalInput, KeyError>;
+}
+
+/// Selects the scan code table of an app. Defaults to the table of the host OS. The
+/// input systems convert the keys of input events with the app's own resource, so
+/// several `App`s in one process can use different tables.
+///
+/// The free `get_scan_code` and `get_key` functions and the `From<KeyCode>` and
+/// `From<ScanCode>` conversions into `UniversalInput` have no app to read the resource
+/// from, so they fall back to a table which is global to the process: the one applied
+/// last with `ActiveScanCodeTable::apply`, or the host table. The `ActionMapPlugin`
+/// applies the resource during `PreStartup` and whenever it changes, so bindings made in
+/// `Startup` systems use the selected table. Bindings built before the app runs, such as
+/// a `make_controls!` passed to `App::insert_resource`, are converted with the host
+/// table unless the table is applied first, or converted with
+/// `ActiveScanCodeTable::input_from_key_code`.
+/// ```rust
+/// use bevy::prelude::*;
+/// use action_maps::prelude::*;
+/// use action_maps::input::{ActiveScanCodeTable, WindowsScanCodes};
+///
+/// let table = ActiveScanCodeTable::new(WindowsScanCodes);
+/// table.apply();
+/// App::new()
+///     .add_plugins(ActionMapPlugin::new())
+///     .insert_resource(table)
+///     .insert_resource(make_controls!(("Up", KeyCode::W)));
+/// ```
+#[derive(Resource, Clone)]
+pub struct ActiveScanCodeTable(pub Arc<dyn ScanCodeTable>);
+
+impl ActiveScanCodeTable {
+    pub fn new<T>(table: T) -> Self
+    where
+        T: ScanCodeTable,
+    {
+        ActiveScanCodeTable(Arc::new(table))
+    }
+
+    /// The table of the host OS.
+    pub fn host() -> Self {
+        match std::env::consts::OS {
+            "macos" => ActiveScanCodeTable::new(MacOsScanCodes),
+            "windows" => ActiveScanCodeTable::new(WindowsScanCodes),
+            "linux" => ActiveScanCodeTable::new(LinuxScanCodes),
+            os => ActiveScanCodeTable::new(UnsupportedOs(os)),
+        }
+    }
+
+    /// The `UniversalInput` of the key at the position of `key` on a QWERTY keyboard,
+    /// converted with this table rather than the applied one.
+    pub fn input_from_key_code(&self, key: KeyCode) -> UniversalInput {
+        let Ok(scan_code) = self.0.scan_code(key.variant_name()) else {
+            warn!("Error KeyCode -> UniversalInput");
+            return UniversalInput::Unknown(0);
+        };
+        self.input_from_scan_code(ScanCode(scan_code))
+    }
+
+    /// The `UniversalInput` of `scan_code`, converted with this table rather than the
+    /// applied one.
+    pub fn input_from_scan_code(&self, scan_code: ScanCode) -> UniversalInput {
+        let Ok(key) = self.0.key(scan_code.0) else {
+            warn!("Error ScanCode -> UniversalInput");
+            return UniversalInput::Unknown(scan_code.0);
+        };
+        key
+    }
+
+    /// Makes this the fallback table used by `get_scan_code`, `get_key` and the `From`
+    /// conversions into `UniversalInput`, in every `App` of the process.
+    pub fn apply(&self) {
+        *ACTIVE_TABLE.write().unwrap_or_else(|e| e.into_inner()) = Some(self.clone());
+    }
+
+    /// The table currently in use.
+    pub fn current() -> Self {
+        ACTIVE_TABLE
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+            .unwrap_or_else(ActiveScanCodeTable::host)
+    }
+}
+
+impl Default for ActiveScanCodeTable {
+    fn default() -> Self {
+        ActiveScanCodeTable::host()
+    }
+}
+
+impl std::fmt::Debug for ActiveScanCodeTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ActiveScanCodeTable")
+            .finish_non_exhaustive()
+    }
+}
+
+static ACTIVE_TABLE: RwLock<Option<ActiveScanCodeTable>> = RwLock::new(None);
+
+pub(crate) fn apply_scan_code_table(table: Res<ActiveScanCodeTable>) {
+    table.apply();
+}
+
+struct UnsupportedOs(&'static str);
+
+impl ScanCodeTable for UnsupportedOs {
+    fn scan_code(&self, _key: &str) -> Result<u32, KeyError> {
+        Err(KeyError::UnsupportedOs(self.0.to_string()))
+    }
+
+    fn key(&self, _scan_code: u32) -> Result<UniversalInput, KeyError> {
+        Err(KeyError::UnsupportedOs(self.0.to_string()))
+    }
+}
+
+/// Key codes of macOS keyboards.
+pub struct MacOsScanCodes;
+
+impl ScanCodeTable for MacOsScanCodes {
+    fn scan_code(&self, key: &str) -> Result<u32, KeyError> {
+        find_scan_code(MACOS_KEYS, MACOS_ALIASES, key)
+    }
+
+    fn key(&self, scan_code: u32) -> Result<UniversalInput, KeyError> {
+        find_key(MACOS_KEYS, scan_code)
+    }
+}
+
+/// Set 1 scan codes used on Windows. The names of the original Windows table, such as
+/// `Esc` and `LShift`, are accepted as aliases.
+pub struct WindowsScanCodes;
+
+impl ScanCodeTable for WindowsScanCodes {
+    fn scan_code(&self, key: &str) -> Result<u32, KeyError> {
+        find_scan_code(WINDOWS_KEYS, WINDOWS_ALIASES, key)
+    }
+
+    fn key(&self, scan_code: u32) -> Result<UniversalInput, KeyError> {
+        find_key(WINDOWS_KEYS, scan_code)
+    }
+}
+
+/// evdev scan codes used on Linux. The names of the Windows table are accepted as
+/// aliases.
+pub struct LinuxScanCodes;
+
+impl ScanCodeTable for LinuxScanCodes {
+    fn scan_code(&self, key: &str) -> Result<u32, KeyError> {
+        find_scan_code(LINUX_KEYS, LINUX_ALIASES, key)
+    }
+
+    fn key(&self, scan_code: u32) -> Result<UniversalInput, KeyError> {
+        find_key(LINUX_KEYS, scan_code)
+    }
+}
+
+fn find_scan_code(
+    keys: &[(UniversalInput, u32)],
+    aliases: &[(&str, UniversalInput)],
+    key: &str,
+) -> Result<u32, KeyError> {
+    let input = aliases
+        .iter()
+        .find(|(alias, _)| *alias == key)
+        .map(|(_, input)| *input);
+
+    keys.iter()
+        .find(|(k, _)| match input {
+            Some(input) => *k == input,
+            None => k.variant_name() == key,
+        })
+        .map(|(_, scan_code)| *scan_code)
+        .ok_or_else(|| KeyError::KeyNotFound(key.to_string()))
+}
+
+fn find_key(
+    keys: &[(UniversalInput, u32)],
+    scan_code: u32,
+) -> Result<UniversalInput, KeyError> {
+    keys.iter()
+        .find(|(_, sc)| *sc == scan_code)
+        .map(|(input, _)| *input)
+        .ok_or_else(|| KeyError::ScanCodeNotFound(scan_code.to_string()))
+}
+
+/// macOS virtual key codes.
+const MACOS_KEYS: &[(UniversalInput, u32)] = &[
+    (UniversalInput::A, 0x00),
+    (UniversalInput::S, 0x01),
+    (UniversalInput::D, 0x02),
+    (UniversalInput::F, 0x03),
+    (UniversalInput::H, 0x04),
+    (UniversalInput::G, 0x05),
+    (UniversalInput::Z, 0x06),
+    (UniversalInput::X, 0x07),
+    (UniversalInput::C, 0x08),
+    (UniversalInput::V, 0x09),
+    (UniversalInput::Oem102, 0x0A),
+    (UniversalInput::B, 0x0B),
+    (UniversalInput::Q, 0x0C),
+    (UniversalInput::W, 0x0D),
+    (UniversalInput::E, 0x0E),
+    (UniversalInput::R, 0x0F),
+    (UniversalInput::Y, 0x10),
+    (UniversalInput::T, 0x11),
+    (UniversalInput::Key1, 0x12),
+    (UniversalInput::Key2, 0x13),
+    (UniversalInput::Key3, 0x14),
+    (UniversalInput::Key4, 0x15),
+    (UniversalInput::Key6, 0x16),
+    (UniversalInput::Key5, 0x17),
+    (UniversalInput::Equals, 0x18),
+    (UniversalInput::Key9, 0x19),
+    (UniversalInput::Key7, 0x1A),
+    (UniversalInput::Minus, 0x1B),
+    (UniversalInput::Key8, 0x1C),
+    (UniversalInput::Key0, 0x1D),
+    (UniversalInput::BracketRight, 0x1E),
+    (UniversalInput::O, 0x1F),
+    (UniversalInput::U, 0x20),
+    (UniversalInput::BracketLeft, 0x21),
+    (UniversalInput::I, 0x22),
+    (UniversalInput::P, 0x23),
+    (UniversalInput::Return, 0x24),
+    (UniversalInput::L, 0x25),
+    (UniversalInput::J, 0x26),
+    (UniversalInput::Apostrophe, 0x27),
+    (UniversalInput::K, 0x28),
+    (UniversalInput::Semicolon, 0x29),
+    (UniversalInput::Backslash, 0x2A),
+    (UniversalInput::Comma, 0x2B),
+    (UniversalInput::Slash, 0x2C),
+    (UniversalInput::N, 0x2D),
+    (UniversalInput::M, 0x2E),
+    (UniversalInput::Period, 0x2F),
+    (UniversalInput::Tab, 0x30),
+    (UniversalInput::Space, 0x31),
+    (UniversalInput::Grave, 0x32),
+    (UniversalInput::Back, 0x33),
+    (UniversalInput::Escape, 0x35),
+    (UniversalInput::SuperRight, 0x36),
+    (UniversalInput::SuperLeft, 0x37),
+    (UniversalInput::ShiftLeft, 0x38),
+    (UniversalInput::Capital, 0x39),
+    (UniversalInput::AltLeft, 0x3A),
+    (UniversalInput::ControlLeft, 0x3B),
+    (UniversalInput::ShiftRight, 0x3C),
+    (UniversalInput::AltRight, 0x3D),
+    (UniversalInput::ControlRight, 0x3E),
+    (UniversalInput::F17, 0x40),
+    (UniversalInput::NumpadDecimal, 0x41),
+    (UniversalInput::NumpadMultiply, 0x43),
+    (UniversalInput::NumpadAdd, 0x45),
+    (UniversalInput::Numlock, 0x47),
+    (UniversalInput::VolumeUp, 0x48),
+    (UniversalInput::VolumeDown, 0x49),
+    (UniversalInput::Mute, 0x4A),
+    (UniversalInput::NumpadDivide, 0x4B),
+    (UniversalInput::NumpadEnter, 0x4C),
+    (UniversalInput::NumpadSubtract, 0x4E),
+    (UniversalInput::F18, 0x4F),
+    (UniversalInput::F19, 0x50),
+    (UniversalInput::NumpadEquals, 0x51),
+    (UniversalInput::Numpad0, 0x52),
+    (UniversalInput::Numpad1, 0x53),
+    (UniversalInput::Numpad2, 0x54),
+    (UniversalInput::Numpad3, 0x55),
+    (UniversalInput::Numpad4, 0x56),
+    (UniversalInput::Numpad5, 0x57),
+    (UniversalInput::Numpad6, 0x58),
+    (UniversalInput::Numpad7, 0x59),
+    (UniversalInput::F20, 0x5A),
+    (UniversalInput::Numpad8, 0x5B),
+    (UniversalInput::Numpad9, 0x5C),
+    (UniversalInput::Yen, 0x5D),
+    (UniversalInput::AbntC1, 0x5E),
+    (UniversalInput::NumpadComma, 0x5F),
+    (UniversalInput::F5, 0x60),
+    (UniversalInput::F6, 0x61),
+    (UniversalInput::F7, 0x62),
+    (UniversalInput::F3, 0x63),
+    (UniversalInput::F8, 0x64),
+    (UniversalInput::F9, 0x65),
+    (UniversalInput::NoConvert, 0x66),
+    (UniversalInput::F11, 0x67),
+    (UniversalInput::Kana, 0x68),
+    (UniversalInput::F13, 0x69),
+    (UniversalInput::F16, 0x6A),
+    (UniversalInput::F14, 0x6B),
+    (UniversalInput::F10, 0x6D),
+    (UniversalInput::Apps, 0x6E),
+    (UniversalInput::F12, 0x6F),
+    (UniversalInput::F15, 0x71),
+    (UniversalInput::Insert, 0x72),
+    (UniversalInput::Home, 0x73),
+    (UniversalInput::PageUp, 0x74),
+    (UniversalInput::Delete, 0x75),
+    (UniversalInput::F4, 0x76),
+    (UniversalInput::End, 0x77),
+    (UniversalInput::F2, 0x78),
+    (UniversalInput::PageDown, 0x79),
+    (UniversalInput::F1, 0x7A),
+    (UniversalInput::Left, 0x7B),
+    (UniversalInput::Right, 0x7C),
+    (UniversalInput::Down, 0x7D),
+    (UniversalInput::Up, 0x7E),
+];
+
+const MACOS_ALIASES: &[(&str, UniversalInput)] = &[
+    ("Equal", UniversalInput::Equals),
+    ("]", UniversalInput::BracketRight),
+    ("[", UniversalInput::BracketLeft),
+    ("Enter", UniversalInput::Return),
+    ("Quote", UniversalInput::Apostrophe),
+    (",", UniversalInput::Comma),
+    (".", UniversalInput::Period),
+    ("Backspace", UniversalInput::Back),
+    ("Esc", UniversalInput::Escape),
+];
+
+/// Set 1 scan codes, with extended keys prefixed by `0xE0` as reported by winit.
+const WINDOWS_KEYS: &[(UniversalInput, u32)] = &[
+    (UniversalInput::Escape, 0x0001),
+    (UniversalInput::Key1, 0x0002),
+    (UniversalInput::Key2, 0x0003),
+    (UniversalInput::Key3, 0x0004),
+    (UniversalInput::Key4, 0x0005),
+    (UniversalInput::Key5, 0x0006),
+    (UniversalInput::Key6, 0x0007),
+    (UniversalInput::Key7, 0x0008),
+    (UniversalInput::Key8, 0x0009),
+    (UniversalInput::Key9, 0x000A),
+    (UniversalInput::Key0, 0x000B),
+    (UniversalInput::Minus, 0x000C),
+    (UniversalInput::Equals, 0x000D),
+    (UniversalInput::Back, 0x000E),
+    (UniversalInput::Tab, 0x000F),
+    (UniversalInput::Q, 0x0010),
+    (UniversalInput::W, 0x0011),
+    (UniversalInput::E, 0x0012),
+    (UniversalInput::R, 0x0013),
+    (UniversalInput::T, 0x0014),
+    (UniversalInput::Y, 0x0015),
+    (UniversalInput::U, 0x0016),
+    (UniversalInput::I, 0x0017),
+    (UniversalInput::O, 0x0018),
+    (UniversalInput::P, 0x0019),
+    (UniversalInput::BracketLeft, 0x001A),
+    (UniversalInput::BracketRight, 0x001B),
+    (UniversalInput::Return, 0x001C),
+    (UniversalInput::ControlLeft, 0x001D),
+    (UniversalInput::A, 0x001E),
+    (UniversalInput::S, 0x001F),
+    (UniversalInput::D, 0x0020),
+    (UniversalInput::F, 0x0021),
+    (UniversalInput::G, 0x0022),
+    (UniversalInput::H, 0x0023),
+    (UniversalInput::J, 0x0024),
+    (UniversalInput::K, 0x0025),
+    (UniversalInput::L, 0x0026),
+    (UniversalInput::Semicolon, 0x0027),
+    (UniversalInput::Apostrophe, 0x0028),
+    (UniversalInput::Grave, 0x0029),
+    (UniversalInput::ShiftLeft, 0x002A),
+    (UniversalInput::Backslash, 0x002B),
+    (UniversalInput::Z, 0x002C),
+    (UniversalInput::X, 0x002D),
+    (UniversalInput::C, 0x002E),
+    (UniversalInput::V, 0x002F),
+    (UniversalInput::B, 0x0030),
+    (UniversalInput::N, 0x0031),
+    (UniversalInput::M, 0x0032),
+    (UniversalInput::Comma, 0x0033),
+    (UniversalInput::Period, 0x0034),
+    (UniversalInput::Slash, 0x0035),
+    (UniversalInput::ShiftRight, 0x0036),
+    (UniversalInput::NumpadMultiply, 0x0037),
+    (UniversalInput::AltLeft, 0x0038),
+    (UniversalInput::Space, 0x0039),
+    (UniversalInput::Capital, 0x003A),
+    (UniversalInput::F1, 0x003B),
+    (UniversalInput::F2, 0x003C),
+    (UniversalInput::F3, 0x003D),
+    (UniversalInput::F4, 0x003E),
+    (UniversalInput::F5, 0x003F),
+    (UniversalInput::F6, 0x0040),
+    (UniversalInput::F7, 0x0041),
+    (UniversalInput::F8, 0x0042),
+    (UniversalInput::F9, 0x0043),
+    (UniversalInput::F10, 0x0044),
+    (UniversalInput::Pause, 0x0045),
+    (UniversalInput::Scroll, 0x0046),
+    (UniversalInput::Numpad7, 0x0047),
+    (UniversalInput::Numpad8, 0x0048),
+    (UniversalInput::Numpad9, 0x0049),
+    (UniversalInput::NumpadSubtract, 0x004A),
+    (UniversalInput::Numpad4, 0x004B),
+    (UniversalInput::Numpad5, 0x004C),
+    (UniversalInput::Numpad6, 0x004D),
+    (UniversalInput::NumpadAdd, 0x004E),
+    (UniversalInput::Numpad1, 0x004F),
+    (UniversalInput::Numpad2, 0x0050),
+    (UniversalInput::Numpad3, 0x0051),
+    (UniversalInput::Numpad0, 0x0052),
+    (UniversalInput::NumpadDecimal, 0x0053),
+    (UniversalInput::Sysrq, 0x0054),
+    (UniversalInput::Oem102, 0x0056),
+    (UniversalInput::F11, 0x0057),
+    (UniversalInput::F12, 0x0058),
+    (UniversalInput::NumpadEquals, 0x0059),
+    (UniversalInput::F13, 0x0064),
+    (UniversalInput::F14, 0x0065),
+    (UniversalInput::F15, 0x0066),
+    (UniversalInput::F16, 0x0067),
+    (UniversalInput::F17, 0x0068),
+    (UniversalInput::F18, 0x0069),
+    (UniversalInput::F19, 0x006A),
+    (UniversalInput::F20, 0x006B),
+    (UniversalInput::F21, 0x006C),
+    (UniversalInput::F22, 0x006D),
+    (UniversalInput::F23, 0x006E),
+    (UniversalInput::Kana, 0x0070),
+    (UniversalInput::AbntC1, 0x0073),
+    (UniversalInput::F24, 0x0076),
+    (UniversalInput::Convert, 0x0079),
+    (UniversalInput::NoConvert, 0x007B),
+    (UniversalInput::Yen, 0x007D),
+    (UniversalInput::NumpadComma, 0x007E),
+    (UniversalInput::Paste, 0xE00A),
+    (UniversalInput::PrevTrack, 0xE010),
+    (UniversalInput::Cut, 0xE017),
+    (UniversalInput::Copy, 0xE018),
+    (UniversalInput::NextTrack, 0xE019),
+    (UniversalInput::NumpadEnter, 0xE01C),
+    (UniversalInput::ControlRight, 0xE01D),
+    (UniversalInput::Mute, 0xE020),
+    (UniversalInput::Calculator, 0xE021),
+    (UniversalInput::PlayPause, 0xE022),
+    (UniversalInput::MediaStop, 0xE024),
+    (UniversalInput::VolumeDown, 0xE02E),
+    (UniversalInput::VolumeUp, 0xE030),
+    (UniversalInput::WebHome, 0xE032),
+    (UniversalInput::NumpadDivide, 0xE035),
+    (UniversalInput::Snapshot, 0xE037),
+    (UniversalInput::AltRight, 0xE038),
+    (UniversalInput::Numlock, 0xE045),
+    (UniversalInput::Home, 0xE047),
+    (UniversalInput::Up, 0xE048),
+    (UniversalInput::PageUp, 0xE049),
+    (UniversalInput::Left, 0xE04B),
+    (UniversalInput::Right, 0xE04D),
+    (UniversalInput::End, 0xE04F),
+    (UniversalInput::Down, 0xE050),
+    (UniversalInput::PageDown, 0xE051),
+    (UniversalInput::Insert, 0xE052),
+    (UniversalInput::Delete, 0xE053),
+    (UniversalInput::SuperLeft, 0xE05B),
+    (UniversalInput::SuperRight, 0xE05C),
+    (UniversalInput::Apps, 0xE05D),
+    (UniversalInput::Power, 0xE05E),
+    (UniversalInput::Sleep, 0xE05F),
+    (UniversalInput::Wake, 0xE063),
+    (UniversalInput::WebSearch, 0xE065),
+    (UniversalInput::WebFavorites, 0xE066),
+    (UniversalInput::WebRefresh, 0xE067),
+    (UniversalInput::WebStop, 0xE068),
+    (UniversalInput::WebForward, 0xE069),
+    (UniversalInput::WebBack, 0xE06A),
+    (UniversalInput::MyComputer, 0xE06B),
+    (UniversalInput::Mail, 0xE06C),
+    (UniversalInput::MediaSelect, 0xE06D),
+];
+
+const WINDOWS_ALIASES: &[(&str, UniversalInput)] = &[
+    ("Esc", UniversalInput::Escape),
+    ("1", UniversalInput::Key1),
+    ("2", UniversalInput::Key2),
+    ("3", UniversalInput::Key3),
+    ("4", UniversalInput::Key4),
+    ("5", UniversalInput::Key5),
+    ("6", UniversalInput::Key6),
+    ("7", UniversalInput::Key7),
+    ("8", UniversalInput::Key8),
+    ("9", UniversalInput::Key9),
+    ("0", UniversalInput::Key0),
+    ("-", UniversalInput::Minus),
+    ("=", UniversalInput::Equals),
+    ("Backspace", UniversalInput::Back),
+    ("[", UniversalInput::BracketLeft),
+    ("]", UniversalInput::BracketRight),
+    ("Enter", UniversalInput::Return),
+    ("Ctrl", UniversalInput::ControlLeft),
+    (";", UniversalInput::Semicolon),
+    ("'", UniversalInput::Apostrophe),
+    ("`", UniversalInput::Grave),
+    ("LShift", UniversalInput::ShiftLeft),
+    ("\\", UniversalInput::Backslash),
+    (",", UniversalInput::Comma),
+    (".", UniversalInput::Period),
+    ("/", UniversalInput::Slash),
+    ("RShift", UniversalInput::ShiftRight),
+    ("Alt", UniversalInput::AltLeft),
+    ("CpsLk", UniversalInput::Capital),
+    ("ScrlLk", UniversalInput::Scroll),
+    ("NumpadUp", UniversalInput::Numpad8),
+    ("Num-", UniversalInput::NumpadSubtract),
+    ("NumpadLeft", UniversalInput::Numpad4),
+    ("NumpadRight", UniversalInput::Numpad6),
+    ("NumpadDown", UniversalInput::Numpad2),
+    ("PtScr", UniversalInput::Snapshot),
+    ("Num", UniversalInput::Numlock),
+    ("Pg", UniversalInput::PageUp),
+    ("PgDown", UniversalInput::PageDown),
+    ("Ins", UniversalInput::Insert),
+    ("Del", UniversalInput::Delete),
+];
+
+/// evdev key codes.
+const LINUX_KEYS: &[(UniversalInput, u32)] = &[
+    (UniversalInput::Escape, 0x01),
+    (UniversalInput::Key1, 0x02),
+    (UniversalInput::Key2, 0x03),
+    (UniversalInput::Key3, 0x04),
+    (UniversalInput::Key4, 0x05),
+    (UniversalInput::Key5, 0x06),
+    (UniversalInput::Key6, 0x07),
+    (UniversalInput::Key7, 0x08),
+    (UniversalInput::Key8, 0x09),
+    (UniversalInput::Key9, 0x0A),
+    (UniversalInput::Key0, 0x0B),
+    (UniversalInput::Minus, 0x0C),
+    (UniversalInput::Equals, 0x0D),
+    (UniversalInput::Back, 0x0E),
+    (UniversalInput::Tab, 0x0F),
+    (UniversalInput::Q, 0x10),
+    (UniversalInput::W, 0x11),
+    (UniversalInput::E, 0x12),
+    (UniversalInput::R, 0x13),
+    (UniversalInput::T, 0x14),
+    (UniversalInput::Y, 0x15),
+    (UniversalInput::U, 0x16),
+    (UniversalInput::I, 0x17),
+    (UniversalInput::O, 0x18),
+    (UniversalInput::P, 0x19),
+    (UniversalInput::BracketLeft, 0x1A),
+    (UniversalInput::BracketRight, 0x1B),
+    (UniversalInput::Return, 0x1C),
+    (UniversalInput::ControlLeft, 0x1D),
+    (UniversalInput::A, 0x1E),
+    (UniversalInput::S, 0x1F),
+    (UniversalInput::D, 0x20),
+    (UniversalInput::F, 0x21),
+    (UniversalInput::G, 0x22),
+    (UniversalInput::H, 0x23),
+    (UniversalInput::J, 0x24),
+    (UniversalInput::K, 0x25),
+    (UniversalInput::L, 0x26),
+    (UniversalInput::Semicolon, 0x27),
+    (UniversalInput::Apostrophe, 0x28),
+    (UniversalInput::Grave, 0x29),
+    (UniversalInput::ShiftLeft, 0x2A),
+    (UniversalInput::Backslash, 0x2B),
+    (UniversalInput::Z, 0x2C),
+    (UniversalInput::X, 0x2D),
+    (UniversalInput::C, 0x2E),
+    (UniversalInput::V, 0x2F),
+    (UniversalInput::B, 0x30),
+    (UniversalInput::N, 0x31),
+    (UniversalInput::M, 0x32),
+    (UniversalInput::Comma, 0x33),
+    (UniversalInput::Period, 0x34),
+    (UniversalInput::Slash, 0x35),
+    (UniversalInput::ShiftRight, 0x36),
+    (UniversalInput::NumpadMultiply, 0x37),
+    (UniversalInput::AltLeft, 0x38),
+    (UniversalInput::Space, 0x39),
+    (UniversalInput::Capital, 0x3A),
+    (UniversalInput::F1, 0x3B),
+    (UniversalInput::F2, 0x3C),
+    (UniversalInput::F3, 0x3D),
+    (UniversalInput::F4, 0x3E),
+    (UniversalInput::F5, 0x3F),
+    (UniversalInput::F6, 0x40),
+    (UniversalInput::F7, 0x41),
+    (UniversalInput::F8, 0x42),
+    (UniversalInput::F9, 0x43),
+    (UniversalInput::F10, 0x44),
+    (UniversalInput::Numlock, 0x45),
+    (UniversalInput::Scroll, 0x46),
+    (UniversalInput::Numpad7, 0x47),
+    (UniversalInput::Numpad8, 0x48),
+    (UniversalInput::Numpad9, 0x49),
+    (UniversalInput::NumpadSubtract, 0x4A),
+    (UniversalInput::Numpad4, 0x4B),
+    (UniversalInput::Numpad5, 0x4C),
+    (UniversalInput::Numpad6, 0x4D),
+    (UniversalInput::NumpadAdd, 0x4E),
+    (UniversalInput::Numpad1, 0x4F),
+    (UniversalInput::Numpad2, 0x50),
+    (UniversalInput::Numpad3, 0x51),
+    (UniversalInput::Numpad0, 0x52),
+    (UniversalInput::NumpadDecimal, 0x53),
+    (UniversalInput::Kanji, 0x55),
+    (UniversalInput::Oem102, 0x56),
+    (UniversalInput::F11, 0x57),
+    (UniversalInput::F12, 0x58),
+    (UniversalInput::AbntC1, 0x59),
+    (UniversalInput::Kana, 0x5A),
+    (UniversalInput::Convert, 0x5C),
+    (UniversalInput::NoConvert, 0x5E),
+    (UniversalInput::NumpadEnter, 0x60),
+    (UniversalInput::ControlRight, 0x61),
+    (UniversalInput::NumpadDivide, 0x62),
+    (UniversalInput::Snapshot, 0x63),
+    (UniversalInput::AltRight, 0x64),
+    (UniversalInput::Home, 0x66),
+    (UniversalInput::Up, 0x67),
+    (UniversalInput::PageUp, 0x68),
+    (UniversalInput::Left, 0x69),
+    (UniversalInput::Right, 0x6A),
+    (UniversalInput::End, 0x6B),
+    (UniversalInput::Down, 0x6C),
+    (UniversalInput::PageDown, 0x6D),
+    (UniversalInput::Insert, 0x6E),
+    (UniversalInput::Delete, 0x6F),
+    (UniversalInput::Mute, 0x71),
+    (UniversalInput::VolumeDown, 0x72),
+    (UniversalInput::VolumeUp, 0x73),
+    (UniversalInput::Power, 0x74),
+    (UniversalInput::NumpadEquals, 0x75),
+    (UniversalInput::Pause, 0x77),
+    (UniversalInput::NumpadComma, 0x79),
+    (UniversalInput::Yen, 0x7C),
+    (UniversalInput::SuperLeft, 0x7D),
+    (UniversalInput::SuperRight, 0x7E),
+    (UniversalInput::Apps, 0x7F),
+    (UniversalInput::Stop, 0x80),
+    (UniversalInput::Copy, 0x85),
+    (UniversalInput::Paste, 0x87),
+    (UniversalInput::Cut, 0x89),
+    (UniversalInput::Calculator, 0x8C),
+    (UniversalInput::Sleep, 0x8E),
+    (UniversalInput::Wake, 0x8F),
+    (UniversalInput::Mail, 0x9B),
+    (UniversalInput::WebFavorites, 0x9C),
+    (UniversalInput::MyComputer, 0x9D),
+    (UniversalInput::WebBack, 0x9E),
+    (UniversalInput::WebForward, 0x9F),
+    (UniversalInput::NextTrack, 0xA3),
+    (UniversalInput::PlayPause, 0xA4),
+    (UniversalInput::PrevTrack, 0xA5),
+    (UniversalInput::MediaStop, 0xA6),
+    (UniversalInput::WebHome, 0xAC),
+    (UniversalInput::WebRefresh, 0xAD),
+    (UniversalInput::F13, 0xB7),
+    (UniversalInput::F14, 0xB8),
+    (UniversalInput::F15, 0xB9),
+    (UniversalInput::F16, 0xBA),
+    (UniversalInput::F17, 0xBB),
+    (UniversalInput::F18, 0xBC),
+    (UniversalInput::F19, 0xBD),
+    (UniversalInput::F20, 0xBE),
+    (UniversalInput::F21, 0xBF),
+    (UniversalInput::F22, 0xC0),
+    (UniversalInput::F23, 0xC1),
+    (UniversalInput::F24, 0xC2),
+    (UniversalInput::WebSearch, 0xD9),
+    (UniversalInput::MediaSelect, 0xE2),
+];
+
+const LINUX_ALIASES: &[(&str, UniversalInput)] = &[
+    ("Esc", UniversalInput::Escape),
+    ("1", UniversalInput::Key1),
+    ("2", UniversalInput::Key2),
+    ("3", UniversalInput::Key3),
+    ("4", UniversalInput::Key4),
+    ("5", UniversalInput::Key5),
+    ("6", UniversalInput::Key6),
+    ("7", UniversalInput::Key7),
+    ("8", UniversalInput::Key8),
+    ("9", UniversalInput::Key9),
+    ("0", UniversalInput::Key0),
+    ("-", UniversalInput::Minus),
+    ("=", UniversalInput::Equals),
+    ("Backspace", UniversalInput::Back),
+    ("[", UniversalInput::BracketLeft),
+    ("]", UniversalInput::BracketRight),
+    ("Enter", UniversalInput::Return),
+    ("Ctrl", UniversalInput::ControlLeft),
+    (";", UniversalInput::Semicolon),
+    ("'", UniversalInput::Apostrophe),
+    ("`", UniversalInput::Grave),
+    ("LShift", UniversalInput::ShiftLeft),
+    ("\\", UniversalInput::Backslash),
+    (",", UniversalInput::Comma),
+    (".", UniversalInput::Period),
+    ("/", UniversalInput::Slash),
+    ("RShift", UniversalInput::ShiftRight),
+    ("Alt", UniversalInput::AltLeft),
+    ("CpsLk", UniversalInput::Capital),
+    ("Num", UniversalInput::Numlock),
+    ("ScrlLk", UniversalInput::Scroll),
+    ("NumpadUp", UniversalInput::Numpad8),
+    ("Num-", UniversalInput::NumpadSubtract),
+    ("NumpadLeft", UniversalInput::Numpad4),
+    ("NumpadRight", UniversalInput::Numpad6),
+    ("NumpadDown", UniversalInput::Numpad2),
+    ("PtScr", UniversalInput::Snapshot),
+    ("Pg", UniversalInput::PageUp),
+    ("PgDown", UniversalInput::PageDown),
+    ("Ins", UniversalInput::Insert),
+    ("Del", UniversalInput::Delete),
+];
@@ -0,0 +1,227 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use thiserror::Error;
+
+use crate::action::ActionType;
+use crate::chord::Chord;
+use crate::control_scheme::{ControlScheme, SharedBinding};
+use crate::dual_axis::DualAxis;
+use crate::input::UniversalInput;
+use crate::interaction::InputInteraction;
+use crate::multi_scheme::MultiScheme;
+
+/// The serialized form of a `ControlScheme`. Bindings are grouped by action name and
+/// sorted, so the same scheme always produces the same output.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub(crate) struct ControlSchemeData {
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    inputs: BTreeMap<String, Vec<UniversalInput>>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    chords: BTreeMap<String, Vec<Chord>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    interactions: Vec<InteractionData>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    dual_axes: BTreeMap<String, Vec<DualAxis>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    shared: Vec<SharedBindingData>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct InteractionData {
+    action: String,
+    input: UniversalInput,
+    interaction: InputInteraction,
+}
+
+/// A saved action name which is not a variant of the action type being loaded.
+#[derive(Debug, Error)]
+#[error("Action {0} does not exist")]
+pub(crate) struct UnknownAction(String);
+
+fn parse_action<A: ActionType>(name: &str) -> Result<A, UnknownAction> {
+    A::from_action_name(name).ok_or_else(|| UnknownAction(name.to_string()))
+}
+
+#[derive(Serialize, Deserialize)]
+struct SharedBindingData {
+    action: String,
+    input: UniversalInput,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    priority: i32,
+    #[serde(default, skip_serializing_if = "is_false")]
+    consume: bool,
+}
+
+fn is_zero(value: &i32) -> bool {
+    *value == 0
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+impl<A: ActionType> From<ControlScheme<A>> for ControlSchemeData {
+    fn from(value: ControlScheme<A>) -> Self {
+        let mut data = ControlSchemeData::default();
+
+        for (input, action) in value.iter() {
+            data.inputs
+                .entry(action.action_name().to_string())
+                .or_default()
+                .push(*input);
+        }
+        data.inputs.values_mut().for_each(|inputs| inputs.sort());
+
+        for (chord, action) in value.iter_chords() {
+            data.chords
+                .entry(action.action_name().to_string())
+                .or_default()
+                .push(chord.clone());
+        }
+        data.chords.values_mut().for_each(|chords| chords.sort());
+
+        let mut interactions = value.iter_interactions().collect::<Vec<_>>();
+        interactions.sort_by_key(|(input, _)| **input);
+        for (input, bindings) in interactions {
+            for (interaction, action) in bindings {
+                data.interactions.push(InteractionData {
+                    action: action.action_name().to_string(),
+                    input: *input,
+                    interaction: *interaction,
+                });
+            }
+        }
+
+        for (action, bindings) in value.iter_dual_axes() {
+            data.dual_axes
+                .insert(action.action_name().to_string(), bindings.clone());
+        }
+
+        // shared bindings keep their order within an input, which breaks priority ties
+        let mut shared = value.iter_shared().collect::<Vec<_>>();
+        shared.sort_by_key(|(input, _)| **input);
+        for (input, bindings) in shared {
+            for binding in bindings {
+                data.shared.push(SharedBindingData {
+                    action: binding.action.action_name().to_string(),
+                    input: *input,
+                    priority: binding.priority,
+                    consume: binding.consume,
+                });
+            }
+        }
+
+        data
+    }
+}
+
+impl<A: ActionType> TryFrom<ControlSchemeData> for ControlScheme<A> {
+    type Error = UnknownAction;
+
+    fn try_from(value: ControlSchemeData) -> Result<Self, Self::Error> {
+        let mut controls = ControlScheme::empty();
+
+        for (action, inputs) in value.inputs {
+            let action = parse_action::<A>(&action)?;
+            for input in inputs {
+                controls.insert(action, input);
+            }
+        }
+
+        for (action, chords) in value.chords {
+            let action = parse_action::<A>(&action)?;
+            for chord in chords {
+                controls.insert_chord(action, chord);
+            }
+        }
+
+        for InteractionData {
+            action,
+            input,
+            interaction,
+        } in value.interactions
+        {
+            let action = parse_action::<A>(&action)?;
+            controls.insert_interaction(action, input, interaction);
+        }
+
+        for (action, bindings) in value.dual_axes {
+            let action = parse_action::<A>(&action)?;
+            for binding in bindings {
+                controls.insert_dual_axis(action, binding);
+            }
+        }
+
+        for SharedBindingData {
+            action,
+            input,
+            priority,
+            consume,
+        } in value.shared
+        {
+            let action = parse_action::<A>(&action)?;
+            controls.insert_shared(
+                input,
+                SharedBinding {
+                    action,
+                    priority,
+                    consume,
+                },
+            );
+        }
+
+        Ok(controls)
+    }
+}
+
+/// The serialized form of a `MultiScheme`, sorted by player id.
+#[derive(Serialize, Deserialize, Default)]
+pub(crate) struct MultiSchemeData {
+    players: Vec<PlayerData>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PlayerData {
+    player: usize,
+    controls: ControlScheme,
+}
+
+impl From<MultiScheme> for MultiSchemeData {
+    fn from(value: MultiScheme) -> Self {
+        let mut players = value
+            .keys()
+            .map(|id| PlayerData {
+                player: *id,
+                controls: value.get(*id).cloned().unwrap_or_default(),
+            })
+            .collect::<Vec<_>>();
+        players.sort_by_key(|player| player.player);
+
+        MultiSchemeData { players }
+    }
+}
+
+impl From<MultiSchemeData> for MultiScheme {
+    fn from(value: MultiSchemeData) -> Self {
+        let mut schemes = MultiScheme::new();
+        for PlayerData { player, controls } in value.players {
+            schemes.insert(player, controls);
+        }
+        schemes
+    }
+}
+
+/// `Chord`s keep their modifiers sorted, so they are deserialized through `Chord::new`.
+#[derive(Deserialize)]
+pub(crate) struct ChordData {
+    modifiers: Vec<UniversalInput>,
+    trigger: UniversalInput,
+}
+
+impl From<ChordData> for Chord {
+    fn from(value: ChordData) -> Self {
+        Chord::new(value.modifiers, value.trigger)
+    }
+}
@@ -2,8 +2,15 @@ use bevy_ecs::change_detection::DetectChangesMut;
 use bevy_ecs::event::Event;
 use bevy_ecs::event::EventReader;
 use bevy_ecs::event::EventWriter;
+use bevy_ecs::system::Local;
+use bevy_ecs::system::Query;
 use bevy_ecs::system::Res;
 use bevy_ecs::system::ResMut;
+use bevy_ecs::system::SystemParam;
+use bevy_input::gamepad::Gamepad;
+use bevy_input::gamepad::GamepadAxis;
+use bevy_input::gamepad::GamepadAxisChangedEvent;
+use bevy_input::gamepad::GamepadAxisType;
 use bevy_input::gamepad::GamepadButton;
 use bevy_input::gamepad::GamepadButtonChangedEvent;
 use bevy_input::gamepad::GamepadButtonInput;
@@ -14,143 +21,503 @@ use bevy_input::keyboard::KeyboardInput;
 use bevy_input::keyboard::ScanCode;
 use bevy_input::mouse::MouseButton;
 use bevy_input::mouse::MouseButtonInput;
+use bevy_input::mouse::MouseMotion;
+use bevy_input::mouse::MouseWheel;
 use bevy_input::ButtonState;
 use bevy_log::warn;
-use bevy_reflect::Enum;
+use bevy_math::Vec2;
+use bevy_reflect::Reflect;
+use bevy_time::Time;
+use std::collections::HashSet;
+use std::time::Duration;
 
+use crate::action::{Action, ActionType};
 use crate::actions::MultiInput;
+use crate::context::{ActionContexts, ActiveControls};
 use crate::controls::MultiScheme;
-use crate::get_key;
-use crate::get_scan_code;
+use crate::gamepad_slot::{GamepadSlot, PLAYER_GAMEPAD};
 use crate::prelude::ActionInput;
 use crate::prelude::ControlScheme;
+use crate::rebind::Rebinding;
+use crate::scan_code::ActiveScanCodeTable;
 
+/// The input events read by the action map systems each frame, along with the
+/// `ActiveScanCodeTable` their keys are converted with.
+#[derive(SystemParam)]
+pub struct InputEventReaders<'w, 's> {
+    table: Option<Res<'w, ActiveScanCodeTable>>,
+    keyboard: EventReader<'w, 's, KeyboardInput>,
+    gamepad_buttons: EventReader<'w, 's, GamepadButtonChangedEvent>,
+    gamepad_axes: EventReader<'w, 's, GamepadAxisChangedEvent>,
+    mouse_buttons: EventReader<'w, 's, MouseButtonInput>,
+    mouse_motion: EventReader<'w, 's, MouseMotion>,
+    mouse_wheel: EventReader<'w, 's, MouseWheel>,
+}
+
+impl InputEventReaders<'_, '_> {
+    pub(crate) fn read(&mut self) -> InputEvents<'_> {
+        // apps without the resource fall back to the table used by `get_key`
+        let table = self
+            .table
+            .as_deref()
+            .cloned()
+            .unwrap_or_else(ActiveScanCodeTable::current);
+        InputEvents {
+            table,
+            keyboard: self.keyboard.read().collect(),
+            gamepad_buttons: self.gamepad_buttons.read().collect(),
+            gamepad_axes: self.gamepad_axes.read().collect(),
+            mouse_buttons: self.mouse_buttons.read().collect(),
+            mouse_motion: self.mouse_motion.read().collect(),
+            mouse_wheel: self.mouse_wheel.read().collect(),
+        }
+    }
+}
+
+pub(crate) struct InputEvents<'a> {
+    table: ActiveScanCodeTable,
+    pub(crate) keyboard: Vec<&'a KeyboardInput>,
+    pub(crate) gamepad_buttons: Vec<&'a GamepadButtonChangedEvent>,
+    pub(crate) gamepad_axes: Vec<&'a GamepadAxisChangedEvent>,
+    pub(crate) mouse_buttons: Vec<&'a MouseButtonInput>,
+    pub(crate) mouse_motion: Vec<&'a MouseMotion>,
+    pub(crate) mouse_wheel: Vec<&'a MouseWheel>,
+}
+
+impl InputEvents<'_> {
+    /// The key at `scan_code` in the app's `ActiveScanCodeTable`.
+    pub(crate) fn key(&self, scan_code: u32) -> UniversalInput {
+        self.table.input_from_scan_code(ScanCode(scan_code))
+    }
+}
+
+/// Reads input events into the `ActionInput` resource according to the active
+/// `ControlScheme`. See `typed_universal_input_system` for typed actions.
 pub fn universal_input_system(
-    mut keyboard_events: EventReader<KeyboardInput>,
-    mut gamepad_events: EventReader<GamepadButtonChangedEvent>,
-    mut button_input_events: EventWriter<GamepadButtonInput>,
-    mut mouse_button_events: EventReader<MouseButtonInput>,
-    mut action_input: ResMut<ActionInput>,
-    control_scheme: Res<ControlScheme>,
+    event_readers: InputEventReaders,
+    action_input: ResMut<ActionInput>,
+    controls: ActiveControls<Action>,
     settings: Res<GamepadSettings>,
+    time: Res<Time>,
+    rebinding: Option<Res<Rebinding>>,
+) {
+    typed_universal_input_system(
+        event_readers,
+        action_input,
+        controls,
+        settings,
+        time,
+        rebinding,
+    );
+}
+
+/// `universal_input_system` for the `ActionInput` and `ControlScheme` of any action
+/// type.
+pub fn typed_universal_input_system<A: ActionType>(
+    mut event_readers: InputEventReaders,
+    mut action_input: ResMut<ActionInput<A>>,
+    mut controls: ActiveControls<A>,
+    settings: Res<GamepadSettings>,
+    time: Res<Time>,
+    rebinding: Option<Res<Rebinding<A>>>,
 ) {
     action_input.bypass_change_detection().clear();
-    let keyboard_events: Vec<&KeyboardInput> = keyboard_events.read().collect();
-    let gamepad_events: Vec<&GamepadButtonChangedEvent> =
-        gamepad_events.read().collect();
-    let mouse_button_events: Vec<&MouseButtonInput> =
-        mouse_button_events.read().collect();
+    if controls.take_release() {
+        action_input.release_everything();
+    }
+    let events = event_readers.read();
+    // inputs pressed while rebinding must not fire actions
+    if rebinding.is_some_and(|rebinding| rebinding.is_suspending()) {
+        action_input.release_everything();
+        track_held_inputs(&events, &mut action_input, &settings, None);
+        return;
+    }
     update_inputs(
-        &keyboard_events,
-        &gamepad_events,
-        &mut button_input_events,
-        &mouse_button_events,
+        &events,
         &mut action_input,
-        &control_scheme,
+        controls.get(),
         &settings,
+        &time,
+        None,
     );
 }
 
+/// Updates the `ActionInput` of every player in the `MultiInput`. Players without a
+/// `ControlScheme` in the `MultiScheme` are treated as having no bindings.
 pub fn multi_universal_input_system(
-    mut keyboard_events: EventReader<KeyboardInput>,
-    mut gamepad_events: EventReader<GamepadButtonChangedEvent>,
-    mut button_input_writer: EventWriter<GamepadButtonInput>,
-    mut mouse_button_events: EventReader<MouseButtonInput>,
+    mut event_readers: InputEventReaders,
     mut multi_input: ResMut<MultiInput>,
     multi_scheme: Res<MultiScheme>,
     settings: Res<GamepadSettings>,
+    time: Res<Time>,
+    mut warned: Local<HashSet<usize>>,
 ) {
     multi_input.bypass_change_detection();
-    let keyboard_events: Vec<&KeyboardInput> = keyboard_events.read().collect();
-    let gamepad_events: Vec<&GamepadButtonChangedEvent> =
-        gamepad_events.read().collect();
-    let mouse_button_events: Vec<&MouseButtonInput> =
-        mouse_button_events.read().collect();
-
-    for i in 0..multi_input.keys().len() {
-        let action_input = multi_input.get_mut(i).unwrap();
-        let control_scheme = multi_scheme.get(i).unwrap();
+    let events = event_readers.read();
+    let unbound = ControlScheme::new();
+
+    let mut players = multi_input.keys().copied().collect::<Vec<_>>();
+    players.sort_unstable();
+    warned.retain(|id| players.contains(id) && multi_scheme.get(*id).is_none());
+
+    for id in players {
+        let gamepad = multi_input.gamepad(id);
+        let control_scheme = multi_scheme.get(id).unwrap_or_else(|| {
+            if warned.insert(id) {
+                warn!("Player {id} has no ControlScheme, so none of their inputs are bound");
+            }
+            &unbound
+        });
+        let Some(action_input) = multi_input.get_mut(id) else {
+            continue;
+        };
         action_input.clear();
         update_inputs(
-            &keyboard_events,
-            &gamepad_events,
-            &mut button_input_writer,
-            &mouse_button_events,
+            &events,
             action_input,
             control_scheme,
             &settings,
+            &time,
+            gamepad,
+        );
+    }
+}
+
+/// Sends a `GamepadButtonInput` whenever a `GamepadButtonChangedEvent` presses a
+/// released button or releases a pressed one. The plugins add it once, however many
+/// `ActionInput`s read the event.
+pub fn gamepad_button_input_system(
+    mut button_events: EventReader<GamepadButtonChangedEvent>,
+    mut button_input_events: EventWriter<GamepadButtonInput>,
+    settings: Res<GamepadSettings>,
+    mut pressed: Local<HashSet<GamepadButton>>,
+) {
+    for event in button_events.read() {
+        let button = GamepadButton::new(event.gamepad, event.button_type);
+        let button_settings = settings.get_button_settings(button);
+        if event.value <= button_settings.release_threshold() {
+            if pressed.remove(&button) {
+                button_input_events.send(GamepadButtonInput {
+                    button,
+                    state: ButtonState::Released,
+                });
+            }
+        } else if event.value >= button_settings.press_threshold()
+            && pressed.insert(button)
+        {
+            button_input_events.send(GamepadButtonInput {
+                button,
+                state: ButtonState::Pressed,
+            });
+        }
+    }
+}
+
+/// Updates every `ActionInput` component from the `ControlScheme` component on the same
+/// entity. An entity without a `ControlScheme` has its held actions and axis values
+/// released, so moving both components to another entity carries the held actions
+/// along, while removing the `ControlScheme` lets go of them.
+///
+/// Like the `ActionInput` resource, the components are suspended while a `Rebinding` is
+/// capturing, and while a blocking `ActionContext` is on the `ActionContexts` stack.
+#[allow(clippy::type_complexity)]
+pub fn entity_input_system<A: ActionType>(
+    mut event_readers: InputEventReaders,
+    mut query: Query<(
+        &mut ActionInput<A>,
+        Option<&ControlScheme<A>>,
+        Option<&GamepadSlot>,
+    )>,
+    settings: Res<GamepadSettings>,
+    time: Res<Time>,
+    rebinding: Option<Res<Rebinding<A>>>,
+    contexts: Option<Res<ActionContexts<A>>>,
+) {
+    let events = event_readers.read();
+    let suspended = rebinding.is_some_and(|rebinding| rebinding.is_suspending())
+        || contexts.is_some_and(|contexts| contexts.blocks_base());
+
+    for (mut action_input, control_scheme, slot) in query.iter_mut() {
+        action_input.bypass_change_detection().clear();
+        let control_scheme = control_scheme.filter(|_| !suspended);
+        let Some(control_scheme) = control_scheme else {
+            action_input.release_everything();
+            track_held_inputs(
+                &events,
+                action_input.bypass_change_detection(),
+                &settings,
+                slot.map(|slot| slot.gamepad),
+            );
+            continue;
+        };
+        update_inputs(
+            &events,
+            &mut action_input,
+            control_scheme,
+            &settings,
+            &time,
+            slot.map(|slot| slot.gamepad),
         );
     }
 }
 
-fn update_inputs(
-    keyboard_events: &Vec<&KeyboardInput>,
-    gamepad_events: &Vec<&GamepadButtonChangedEvent>,
-    button_input_events: &mut EventWriter<GamepadButtonInput>,
-    mouse_button_events: &Vec<&MouseButtonInput>,
-    action_input: &mut ActionInput,
-    control_scheme: &ControlScheme,
+fn update_inputs<A: ActionType>(
+    events: &InputEvents,
+    action_input: &mut ActionInput<A>,
+    control_scheme: &ControlScheme<A>,
     settings: &Res<GamepadSettings>,
+    time: &Time,
+    player_gamepad: Option<Gamepad>,
 ) {
-    for event in keyboard_events {
+    let now = time.elapsed();
+
+    // the mouse wheel has no release event, so wheel inputs are only held for a frame
+    for wheel in MOUSE_WHEEL_INPUTS {
+        if action_input.input_pressed(wheel) {
+            update_button(
+                wheel,
+                ButtonState::Released,
+                action_input,
+                control_scheme,
+                now,
+            );
+        }
+    }
+
+    for event in &events.keyboard {
         let KeyboardInput {
             scan_code, state, ..
         } = event;
 
-        let key: UniversalInput = ScanCode(*scan_code).into();
-        if let Some(action) = control_scheme.get(key) {
-            match state {
-                ButtonState::Pressed => action_input.press(*action),
-                ButtonState::Released => action_input.release(*action),
-            }
-        }
+        let key = events.key(*scan_code);
+        update_button(key, *state, action_input, control_scheme, now);
     }
 
-    for event in gamepad_events {
+    for event in &events.gamepad_buttons {
         let button = GamepadButton::new(event.gamepad, event.button_type);
         let value = event.value;
         let button_settings = settings.get_button_settings(button);
-        let input: UniversalInput = button.into();
-        let Some(action) = control_scheme.get(input) else {
-            return;
-        };
+        let inputs = gamepad_inputs(button.into(), event.gamepad, player_gamepad);
 
         // if is released...
-        if value <= button_settings.release_threshold() {
-            if action_input.pressed(*action) {
-                button_input_events.send(GamepadButtonInput {
-                    button,
-                    state: ButtonState::Released,
-                });
-            }
-            action_input.release(*action);
+        let state = if value <= button_settings.release_threshold() {
+            ButtonState::Released
         } else if value >= button_settings.press_threshold() {
-            button_input_events.send(GamepadButtonInput {
-                button,
-                state: ButtonState::Pressed,
-            });
-            action_input.press(*action);
+            ButtonState::Pressed
+        } else {
+            continue;
+        };
+
+        for input in inputs {
+            update_button(input, state, action_input, control_scheme, now);
+        }
+    }
+
+    for event in &events.gamepad_axes {
+        let axis = GamepadAxis::new(event.gamepad, event.axis_type);
+        for input in gamepad_inputs(axis.into(), event.gamepad, player_gamepad) {
+            action_input.set_input_axis(input, event.value);
+            for action in control_scheme.actions_for(input) {
+                action_input.set_axis(action, event.value);
+            }
         }
     }
 
-    for event in mouse_button_events {
+    for event in &events.mouse_buttons {
         let button: UniversalInput = event.button.into();
+        update_button(button, event.state, action_input, control_scheme, now);
+    }
+
+    for event in &events.mouse_wheel {
+        let directions = [
+            (event.y > 0., UniversalInput::MouseWheelUp),
+            (event.y < 0., UniversalInput::MouseWheelDown),
+            (event.x < 0., UniversalInput::MouseWheelLeft),
+            (event.x > 0., UniversalInput::MouseWheelRight),
+        ];
+        for (scrolled, wheel) in directions {
+            if scrolled {
+                update_button(
+                    wheel,
+                    ButtonState::Pressed,
+                    action_input,
+                    control_scheme,
+                    now,
+                );
+            }
+        }
+    }
+
+    // mouse motion is the total delta of this frame, rather than a held value
+    let delta = events
+        .mouse_motion
+        .iter()
+        .map(|event| event.delta)
+        .sum::<Vec2>();
+    for (input, value) in [
+        (UniversalInput::MouseMotionX, delta.x),
+        (UniversalInput::MouseMotionY, delta.y),
+    ] {
+        action_input.set_input_axis(input, value);
+        for action in control_scheme.actions_for(input) {
+            action_input.set_axis(action, value);
+        }
+    }
+
+    for (input, bindings) in control_scheme.iter_interactions() {
+        if action_input.input_pressed(*input) {
+            for action in action_input.interactions_mut().hold(*input, now, bindings) {
+                action_input.trigger(action);
+            }
+        }
+    }
+
+    // sticks and buttons are clamped to a unit circle, mouse motion is not
+    for (action, bindings) in control_scheme.iter_dual_axes() {
+        let sum = |bounded: bool| {
+            bindings
+                .iter()
+                .filter(|binding| binding.is_bounded() == bounded)
+                .map(|binding| binding.value(action_input))
+                .sum::<Vec2>()
+        };
+        let value = sum(true).clamp_length_max(1.) + sum(false);
+        action_input.set_dual_axis(*action, value);
+    }
+}
+
+/// Presses and releases the inputs of the events without dispatching any actions, so
+/// inputs held while actions are suspended are known to be held afterwards.
+fn track_held_inputs<A: ActionType>(
+    events: &InputEvents,
+    action_input: &mut ActionInput<A>,
+    settings: &GamepadSettings,
+    player_gamepad: Option<Gamepad>,
+) {
+    let mut track = |input: UniversalInput, state: ButtonState| match state {
+        ButtonState::Pressed => action_input.press_input(input),
+        ButtonState::Released => action_input.release_input(input),
+    };
+
+    for event in &events.keyboard {
+        track(events.key(event.scan_code), event.state);
+    }
+
+    for event in &events.mouse_buttons {
+        track(event.button.into(), event.state);
+    }
+
+    for event in &events.gamepad_buttons {
+        let button = GamepadButton::new(event.gamepad, event.button_type);
+        let button_settings = settings.get_button_settings(button);
+        let state = if event.value <= button_settings.release_threshold() {
+            ButtonState::Released
+        } else if event.value >= button_settings.press_threshold() {
+            ButtonState::Pressed
+        } else {
+            continue;
+        };
+        for input in gamepad_inputs(button.into(), event.gamepad, player_gamepad) {
+            track(input, state);
+        }
+    }
+}
+
+/// The inputs a gamepad event is read as: the input itself, and if the event comes from
+/// the player's own gamepad, the same input on `PLAYER_GAMEPAD`.
+fn gamepad_inputs(
+    input: UniversalInput,
+    gamepad: Gamepad,
+    player_gamepad: Option<Gamepad>,
+) -> Vec<UniversalInput> {
+    if player_gamepad == Some(gamepad) {
+        vec![input, input.with_gamepad(PLAYER_GAMEPAD.id)]
+    } else {
+        vec![input]
+    }
+}
+
+fn update_button<A: ActionType>(
+    input: UniversalInput,
+    state: ButtonState,
+    action_input: &mut ActionInput<A>,
+    control_scheme: &ControlScheme<A>,
+    now: Duration,
+) {
+    // the OS repeats the pressed event of a held key, which is not a new press
+    if state == ButtonState::Pressed && action_input.input_pressed(input) {
+        return;
+    }
+
+    let interactions = control_scheme.get_interactions(input);
+    let triggered = match state {
+        ButtonState::Pressed => {
+            action_input
+                .interactions_mut()
+                .press(input, now, interactions)
+        }
+        ButtonState::Released => {
+            action_input
+                .interactions_mut()
+                .release(input, now, interactions)
+        }
+    };
+    for action in triggered {
+        action_input.trigger(action);
+    }
+
+    match state {
+        ButtonState::Pressed => {
+            action_input.press_input(input);
+            let chord = control_scheme
+                .matching_chord(input, |modifier| action_input.input_pressed(modifier))
+                .map(|(chord, action)| (chord.clone(), *action));
 
-        if let Some(action) = control_scheme.get(button) {
-            match event.state {
-                ButtonState::Pressed => action_input.press(*action),
-                ButtonState::Released => action_input.release(*action),
+            // a chord wins over the actions bound to its bare trigger
+            if let Some((chord, action)) = chord {
+                action_input.press(action);
+                action_input.activate_chord(chord, action);
+            } else {
+                for action in control_scheme.actions_for(input) {
+                    action_input.press(action);
+                }
+            }
+        }
+        ButtonState::Released => {
+            action_input.release_input(input);
+            // releasing the trigger or any of the modifiers releases the chord
+            for action in action_input.deactivate_chords(input) {
+                action_input.release(action);
+            }
+            for action in control_scheme.actions_for(input) {
+                action_input.release(action);
             }
         }
     }
 }
 
+const MOUSE_WHEEL_INPUTS: [UniversalInput; 4] = [
+    UniversalInput::MouseWheelUp,
+    UniversalInput::MouseWheelDown,
+    UniversalInput::MouseWheelLeft,
+    UniversalInput::MouseWheelRight,
+];
+
 #[derive(Event)]
-pub struct UniversalInputEvent(UniversalInput);
+pub struct UniversalInputEvent(pub UniversalInput);
+
+/// The kind of device a `UniversalInput` comes from.
+#[derive(Hash, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InputKind {
+    Keyboard,
+    Mouse,
+    Gamepad,
+}
 
 /// Keys represent the physical key
 #[repr(u32)]
-#[derive(Hash, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Hash, Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum UniversalInput {
     /// The `1` key over the letters.
     Key1,
@@ -551,6 +918,21 @@ pub enum UniversalInput {
     /// Miscellaneous buttons, considered non-standard (i.e. Extra buttons on a flight stick that do not have a gamepad equivalent).
     GamepadOther(u8, usize),
 
+    /// The horizontal value of the left stick.
+    GamepadLeftStickX(usize),
+    /// The vertical value of the left stick.
+    GamepadLeftStickY(usize),
+    /// The value of the left `Z` button.
+    GamepadLeftZ(usize),
+    /// The horizontal value of the right stick.
+    GamepadRightStickX(usize),
+    /// The vertical value of the right stick.
+    GamepadRightStickY(usize),
+    /// The value of the right `Z` button.
+    GamepadRightZ(usize),
+    /// Miscellaneous axes, considered non-standard (i.e. Extra axes on a flight stick that do not have a gamepad equivalent).
+    GamepadAxisOther(u8, usize),
+
     /// The left mouse button.
     MouseLeft,
     /// The right mouse button.
@@ -560,37 +942,126 @@ pub enum UniversalInput {
     /// Another mouse button with the associated number.
     MouseOther(u16),
 
+    /// The mouse wheel scrolled up.
+    MouseWheelUp,
+    /// The mouse wheel scrolled down.
+    MouseWheelDown,
+    /// The mouse wheel scrolled left.
+    MouseWheelLeft,
+    /// The mouse wheel scrolled right.
+    MouseWheelRight,
+
+    /// The horizontal movement of the mouse this frame.
+    MouseMotionX,
+    /// The vertical movement of the mouse this frame.
+    MouseMotionY,
+
     /// Represents an input button that is not accessible
     /// Most often occurs when there was an error converting to a UniversalInput
     Unknown(u32),
 }
 
+impl UniversalInput {
+    pub fn kind(&self) -> InputKind {
+        use UniversalInput::*;
+
+        match self {
+            GamepadSouth(_)
+            | GamepadEast(_)
+            | GamepadNorth(_)
+            | GamepadWest(_)
+            | GamepadC(_)
+            | GamepadZ(_)
+            | GamepadLeftTrigger(_)
+            | GamepadLeftTrigger2(_)
+            | GamepadRightTrigger(_)
+            | GamepadRightTrigger2(_)
+            | GamepadSelect(_)
+            | GamepadStart(_)
+            | GamepadMode(_)
+            | GamepadLeftThumb(_)
+            | GamepadRightThumb(_)
+            | GamepadDPadUp(_)
+            | GamepadDPadDown(_)
+            | GamepadDPadLeft(_)
+            | GamepadDPadRight(_)
+            | GamepadOther(..)
+            | GamepadLeftStickX(_)
+            | GamepadLeftStickY(_)
+            | GamepadLeftZ(_)
+            | GamepadRightStickX(_)
+            | GamepadRightStickY(_)
+            | GamepadRightZ(_)
+            | GamepadAxisOther(..) => InputKind::Gamepad,
+            MouseLeft | MouseRight | MouseMiddle | MouseOther(_) | MouseWheelUp
+            | MouseWheelDown | MouseWheelLeft | MouseWheelRight | MouseMotionX
+            | MouseMotionY => InputKind::Mouse,
+            // unknown inputs come from unrecognized scan codes
+            _ => InputKind::Keyboard,
+        }
+    }
+
+    /// The id of the gamepad a gamepad input comes from.
+    pub fn gamepad(&self) -> Option<usize> {
+        let mut input = *self;
+        input.gamepad_mut().map(|id| *id)
+    }
+
+    /// The same input on another gamepad. Inputs of other devices are returned as is.
+    pub fn with_gamepad(mut self, gamepad: usize) -> Self {
+        if let Some(id) = self.gamepad_mut() {
+            *id = gamepad;
+        }
+        self
+    }
+
+    fn gamepad_mut(&mut self) -> Option<&mut usize> {
+        use UniversalInput::*;
+
+        match self {
+            GamepadSouth(id)
+            | GamepadEast(id)
+            | GamepadNorth(id)
+            | GamepadWest(id)
+            | GamepadC(id)
+            | GamepadZ(id)
+            | GamepadLeftTrigger(id)
+            | GamepadLeftTrigger2(id)
+            | GamepadRightTrigger(id)
+            | GamepadRightTrigger2(id)
+            | GamepadSelect(id)
+            | GamepadStart(id)
+            | GamepadMode(id)
+            | GamepadLeftThumb(id)
+            | GamepadRightThumb(id)
+            | GamepadDPadUp(id)
+            | GamepadDPadDown(id)
+            | GamepadDPadLeft(id)
+            | GamepadDPadRight(id)
+            | GamepadOther(_, id)
+            | GamepadLeftStickX(id)
+            | GamepadLeftStickY(id)
+            | GamepadLeftZ(id)
+            | GamepadRightStickX(id)
+            | GamepadRightStickY(id)
+            | GamepadRightZ(id)
+            | GamepadAxisOther(_, id) => Some(id),
+            _ => None,
+        }
+    }
+}
+
 impl From<KeyCode> for UniversalInput {
     fn from(value: KeyCode) -> Self {
         // Key names are named exactly as they're key code.
         // Must first convert to physical key location
-        let key_str = value.variant_name();
-        let Ok(scan_code) = get_scan_code(key_str) else {
-            warn!("Error KeyCode -> UniversalInput");
-            return UniversalInput::Unknown(0);
-        };
-        let Ok(scan_code) = get_key(scan_code) else {
-            warn!("Error KeyCode -> UniversalInput");
-            return UniversalInput::Unknown(scan_code);
-        };
-
-        scan_code
+        ActiveScanCodeTable::current().input_from_key_code(value)
     }
 }
 
 impl From<ScanCode> for UniversalInput {
     fn from(value: ScanCode) -> Self {
-        let Ok(scan_code) = get_key(value.0) else {
-            warn!("Error ScanCode -> UniversalInput");
-            return UniversalInput::Unknown(value.0);
-        };
-
-        scan_code
+        ActiveScanCodeTable::current().input_from_scan_code(value)
     }
 }
 
@@ -626,6 +1097,23 @@ impl From<GamepadButton> for UniversalInput {
     }
 }
 
+impl From<GamepadAxis> for UniversalInput {
+    fn from(value: GamepadAxis) -> Self {
+        let axis_type = value.axis_type;
+        let id = value.gamepad.id;
+
+        match axis_type {
+            GamepadAxisType::LeftStickX => UniversalInput::GamepadLeftStickX(id),
+            GamepadAxisType::LeftStickY => UniversalInput::GamepadLeftStickY(id),
+            GamepadAxisType::LeftZ => UniversalInput::GamepadLeftZ(id),
+            GamepadAxisType::RightStickX => UniversalInput::GamepadRightStickX(id),
+            GamepadAxisType::RightStickY => UniversalInput::GamepadRightStickY(id),
+            GamepadAxisType::RightZ => UniversalInput::GamepadRightZ(id),
+            GamepadAxisType::Other(c) => UniversalInput::GamepadAxisOther(c, id),
+        }
+    }
+}
+
 impl From<MouseButton> for UniversalInput {
     fn from(value: MouseButton) -> Self {
         match value {
@@ -1,6 +1,51 @@
 use action_maps::prelude::*;
 use bevy::prelude::*;
-use bevy_input::{keyboard::KeyboardInput, ButtonState};
+use bevy_input::{
+    gamepad::GamepadAxisChangedEvent, keyboard::KeyboardInput, ButtonState,
+};
+
+/// An app which reads input events into the `ActionInput` resource with
+/// `universal_input_system`, bound with `controls`.
+fn input_app(controls: ControlScheme) -> App {
+    let mut app = App::new();
+
+    app.configure_sets(
+        PreUpdate,
+        ActionMapSet::HandleActions.after(ActionMapSet::ReadEvents),
+    )
+    .add_event::<bevy::input::keyboard::KeyboardInput>()
+    .add_event::<bevy::input::gamepad::GamepadButtonChangedEvent>()
+    .add_event::<bevy::input::gamepad::GamepadAxisChangedEvent>()
+    .add_event::<bevy::input::gamepad::GamepadButtonInput>()
+    .add_event::<bevy::input::mouse::MouseButtonInput>()
+    .add_event::<bevy::input::mouse::MouseMotion>()
+    .add_event::<bevy::input::mouse::MouseWheel>()
+    .insert_resource(bevy::input::gamepad::GamepadSettings::default())
+    .init_resource::<Time>()
+    .add_systems(
+        PreUpdate,
+        (action_maps::input::universal_input_system).in_set(ActionMapSet::ReadEvents),
+    );
+    app.insert_resource(controls);
+    app.insert_resource(ActionInput::default());
+    app.update();
+    app
+}
+
+fn key(scan_code: u32, state: ButtonState) -> KeyboardInput {
+    KeyboardInput {
+        scan_code,
+        key_code: None,
+        state,
+        window: bevy_ecs::entity::Entity::from_raw(0),
+    }
+}
+
+fn send_key(app: &mut App, scan_code: u32, state: ButtonState) {
+    app.world
+        .resource_mut::<Events<KeyboardInput>>()
+        .send(key(scan_code, state));
+}
 
 #[test]
 fn resource_responds_to_update() {
@@ -12,9 +57,13 @@ fn resource_responds_to_update() {
     )
     .add_event::<bevy::input::keyboard::KeyboardInput>()
     .add_event::<bevy::input::gamepad::GamepadButtonChangedEvent>()
+    .add_event::<bevy::input::gamepad::GamepadAxisChangedEvent>()
     .add_event::<bevy::input::gamepad::GamepadButtonInput>()
     .add_event::<bevy::input::mouse::MouseButtonInput>()
+    .add_event::<bevy::input::mouse::MouseMotion>()
+    .add_event::<bevy::input::mouse::MouseWheel>()
     .insert_resource(bevy::input::gamepad::GamepadSettings::default())
+    .init_resource::<Time>()
     .add_systems(
         PreUpdate,
         (action_maps::input::universal_input_system).in_set(ActionMapSet::ReadEvents),
@@ -64,3 +113,553 @@ fn resource_responds_to_update() {
     let ai = app.world.resource::<ActionInput>();
     assert!(!ai.pressed("A"));
 }
+
+#[test]
+fn axis_responds_to_update() {
+    let gamepad = Gamepad::new(0);
+    let mut cs = ControlScheme::default();
+    cs.insert(
+        "MoveX",
+        GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX),
+    );
+    let mut app = input_app(cs);
+
+    let ai = app.world.resource::<ActionInput>();
+    assert_eq!(ai.axis("MoveX"), 0.);
+
+    // move the stick
+    app.world
+        .resource_mut::<Events<GamepadAxisChangedEvent>>()
+        .send(GamepadAxisChangedEvent::new(
+            gamepad,
+            GamepadAxisType::LeftStickX,
+            -0.5,
+        ));
+    app.update();
+    let ai = app.world.resource::<ActionInput>();
+    assert_eq!(ai.axis("MoveX"), -0.5);
+
+    // the value is held until the axis changes again
+    app.update();
+    let ai = app.world.resource::<ActionInput>();
+    assert_eq!(ai.axis("MoveX"), -0.5);
+
+    // axes on other gamepads are not bound
+    app.world
+        .resource_mut::<Events<GamepadAxisChangedEvent>>()
+        .send(GamepadAxisChangedEvent::new(
+            Gamepad::new(1),
+            GamepadAxisType::LeftStickX,
+            1.,
+        ));
+    app.update();
+    let ai = app.world.resource::<ActionInput>();
+    assert_eq!(ai.axis("MoveX"), -0.5);
+}
+
+#[test]
+fn dual_axis_responds_to_update() {
+    let gamepad = Gamepad::new(0);
+    let mut cs = ControlScheme::default();
+    cs.insert_dual_axis(
+        "Move",
+        DualAxis::buttons(
+            ScanCode(0x11),
+            ScanCode(0x1F),
+            ScanCode(0x1E),
+            ScanCode(0x20),
+        ),
+    );
+    cs.insert_dual_axis("Move", DualAxis::left_stick(gamepad));
+    let mut app = input_app(cs);
+
+    let ai = app.world.resource::<ActionInput>();
+    assert_eq!(ai.dual_axis("Move"), Vec2::ZERO);
+
+    // press up
+    send_key(&mut app, 0x11, ButtonState::Pressed);
+    app.update();
+    let ai = app.world.resource::<ActionInput>();
+    assert_eq!(ai.dual_axis("Move"), Vec2::Y);
+
+    // press right, diagonals are normalized
+    send_key(&mut app, 0x20, ButtonState::Pressed);
+    app.update();
+    let ai = app.world.resource::<ActionInput>();
+    assert!((ai.dual_axis("Move") - Vec2::ONE.normalize()).length() < 1e-6);
+
+    // release both and use the stick instead
+    let mut keyboard_events = app.world.resource_mut::<Events<KeyboardInput>>();
+    keyboard_events.send(key(0x11, ButtonState::Released));
+    keyboard_events.send(key(0x20, ButtonState::Released));
+    let mut axis_events = app.world.resource_mut::<Events<GamepadAxisChangedEvent>>();
+    axis_events.send(GamepadAxisChangedEvent::new(
+        gamepad,
+        GamepadAxisType::LeftStickX,
+        -0.5,
+    ));
+    axis_events.send(GamepadAxisChangedEvent::new(
+        gamepad,
+        GamepadAxisType::LeftStickY,
+        0.25,
+    ));
+    app.update();
+    let ai = app.world.resource::<ActionInput>();
+    assert_eq!(ai.dual_axis("Move"), Vec2::new(-0.5, 0.25));
+}
+
+#[test]
+fn mouse_wheel_and_motion_respond_to_update() {
+    use action_maps::input::UniversalInput;
+    use bevy::input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel};
+
+    let mut cs = ControlScheme::default();
+    cs.insert("NextWeapon", UniversalInput::MouseWheelUp);
+    cs.insert("PreviousWeapon", UniversalInput::MouseWheelDown);
+    cs.insert("LookX", UniversalInput::MouseMotionX);
+    cs.insert_dual_axis("Look", DualAxis::mouse_motion());
+    let mut app = input_app(cs);
+
+    // scroll up
+    app.world
+        .resource_mut::<Events<MouseWheel>>()
+        .send(MouseWheel {
+            unit: MouseScrollUnit::Line,
+            x: 0.,
+            y: 1.,
+            window: bevy_ecs::entity::Entity::from_raw(0),
+        });
+    app.update();
+    let ai = app.world.resource::<ActionInput>();
+    assert!(ai.just_pressed("NextWeapon"));
+    assert!(!ai.pressed("PreviousWeapon"));
+
+    // the wheel is released on the next frame
+    app.update();
+    let ai = app.world.resource::<ActionInput>();
+    assert!(!ai.pressed("NextWeapon"));
+    assert!(ai
+        .get_just_released()
+        .any(|action| *action == "NextWeapon".into()));
+
+    // move the mouse
+    let mut motion_events = app.world.resource_mut::<Events<MouseMotion>>();
+    motion_events.send(MouseMotion {
+        delta: Vec2::new(0.25, 0.5),
+    });
+    motion_events.send(MouseMotion {
+        delta: Vec2::new(0.25, 0.),
+    });
+    app.update();
+    let ai = app.world.resource::<ActionInput>();
+    assert_eq!(ai.axis("LookX"), 0.5);
+    assert_eq!(ai.dual_axis("Look"), Vec2::new(0.5, 0.5));
+
+    // mouse motion is measured in pixels, so it is not clamped
+    app.world
+        .resource_mut::<Events<MouseMotion>>()
+        .send(MouseMotion {
+            delta: Vec2::new(30., -40.),
+        });
+    app.update();
+    let ai = app.world.resource::<ActionInput>();
+    assert_eq!(ai.axis("LookX"), 30.);
+    assert_eq!(ai.dual_axis("Look"), Vec2::new(30., -40.));
+
+    // mouse motion does not carry over to the next frame
+    app.update();
+    let ai = app.world.resource::<ActionInput>();
+    assert_eq!(ai.axis("LookX"), 0.);
+    assert_eq!(ai.dual_axis("Look"), Vec2::ZERO);
+}
+
+#[test]
+fn chord_wins_over_bare_trigger() {
+    use bevy::input::gamepad::GamepadButtonChangedEvent;
+
+    let button = |button_type: GamepadButtonType, value: f32| {
+        GamepadButtonChangedEvent::new(Gamepad::new(0), button_type, value)
+    };
+
+    let gamepad = Gamepad::new(0);
+    let left_bumper = GamepadButton::new(gamepad, GamepadButtonType::LeftTrigger);
+    let south = GamepadButton::new(gamepad, GamepadButtonType::South);
+    let mut cs = ControlScheme::default();
+    cs.insert("Down", ScanCode(0x1F));
+    cs.insert_chord("Save", Chord::new([ScanCode(0x1D)], ScanCode(0x1F)));
+    cs.insert("Jump", south);
+    cs.insert_chord("Parry", Chord::new([left_bumper], south));
+    let mut app = input_app(cs);
+
+    // the bare trigger still works on its own
+    send_key(&mut app, 0x1F, ButtonState::Pressed);
+    app.update();
+    let ai = app.world.resource::<ActionInput>();
+    assert!(ai.pressed("Down"));
+    assert!(!ai.pressed("Save"));
+
+    send_key(&mut app, 0x1F, ButtonState::Released);
+    app.update();
+
+    // holding the modifier fires the chord instead
+    let mut keyboard_events = app.world.resource_mut::<Events<KeyboardInput>>();
+    keyboard_events.send(key(0x1D, ButtonState::Pressed));
+    keyboard_events.send(key(0x1F, ButtonState::Pressed));
+    app.update();
+    let ai = app.world.resource::<ActionInput>();
+    assert!(ai.just_pressed("Save"));
+    assert!(!ai.pressed("Down"));
+
+    // releasing the modifier releases the chord
+    send_key(&mut app, 0x1D, ButtonState::Released);
+    app.update();
+    let ai = app.world.resource::<ActionInput>();
+    assert!(!ai.pressed("Save"));
+    assert!(!ai.pressed("Down"));
+
+    // gamepad chords behave the same way
+    let mut button_events = app
+        .world
+        .resource_mut::<Events<GamepadButtonChangedEvent>>();
+    button_events.send(button(GamepadButtonType::LeftTrigger, 1.));
+    button_events.send(button(GamepadButtonType::South, 1.));
+    app.update();
+    let ai = app.world.resource::<ActionInput>();
+    assert!(ai.just_pressed("Parry"));
+    assert!(!ai.pressed("Jump"));
+
+    let mut button_events = app
+        .world
+        .resource_mut::<Events<GamepadButtonChangedEvent>>();
+    button_events.send(button(GamepadButtonType::South, 0.));
+    app.update();
+    let ai = app.world.resource::<ActionInput>();
+    assert!(!ai.pressed("Parry"));
+}
+
+#[test]
+fn interactions_trigger_on_timing() {
+    use std::time::Duration;
+
+    let send = |app: &mut App, event: KeyboardInput, elapsed: u64| {
+        app.world
+            .resource_mut::<Events<KeyboardInput>>()
+            .send(event);
+        app.world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(elapsed));
+        app.update();
+    };
+
+    let mut cs = ControlScheme::default();
+    cs.insert_interaction(
+        "Reload",
+        ScanCode(0x13),
+        InputInteraction::tap(Duration::from_millis(200)),
+    );
+    cs.insert_interaction(
+        "Inspect",
+        ScanCode(0x13),
+        InputInteraction::hold(Duration::from_millis(500)),
+    );
+    cs.insert_interaction(
+        "Dash",
+        ScanCode(0x20),
+        InputInteraction::double_tap(Duration::from_millis(250)),
+    );
+    let mut app = input_app(cs);
+
+    // a short press is a tap
+    send(&mut app, key(0x13, ButtonState::Pressed), 0);
+    assert!(!app.world.resource::<ActionInput>().just_triggered("Reload"));
+    send(&mut app, key(0x13, ButtonState::Released), 100);
+    let ai = app.world.resource::<ActionInput>();
+    assert!(ai.just_triggered("Reload"));
+    assert!(!ai.just_triggered("Inspect"));
+
+    // triggers only last for a single frame
+    app.update();
+    assert!(!app.world.resource::<ActionInput>().just_triggered("Reload"));
+
+    // a long press is a hold, which triggers while still held
+    send(&mut app, key(0x13, ButtonState::Pressed), 0);
+    app.world
+        .resource_mut::<Time>()
+        .advance_by(Duration::from_millis(600));
+    app.update();
+    assert!(app
+        .world
+        .resource::<ActionInput>()
+        .just_triggered("Inspect"));
+    app.update();
+    assert!(!app
+        .world
+        .resource::<ActionInput>()
+        .just_triggered("Inspect"));
+    send(&mut app, key(0x13, ButtonState::Released), 100);
+    let ai = app.world.resource::<ActionInput>();
+    assert!(!ai.just_triggered("Reload"));
+    assert!(!ai.just_triggered("Inspect"));
+
+    // two presses within the window are a double tap
+    send(&mut app, key(0x20, ButtonState::Pressed), 0);
+    send(&mut app, key(0x20, ButtonState::Released), 50);
+    send(&mut app, key(0x20, ButtonState::Pressed), 100);
+    assert!(app.world.resource::<ActionInput>().just_triggered("Dash"));
+    send(&mut app, key(0x20, ButtonState::Released), 50);
+
+    // two presses too far apart are not
+    send(&mut app, key(0x20, ButtonState::Pressed), 1000);
+    send(&mut app, key(0x20, ButtonState::Released), 50);
+    send(&mut app, key(0x20, ButtonState::Pressed), 400);
+    assert!(!app.world.resource::<ActionInput>().just_triggered("Dash"));
+}
+
+#[test]
+fn rebinding_captures_next_input() {
+    use bevy::input::gamepad::GamepadButtonChangedEvent;
+    use std::time::Duration;
+
+    let rebind_events = |app: &mut App| {
+        app.world
+            .resource_mut::<Events<RebindEvent>>()
+            .drain()
+            .collect::<Vec<_>>()
+    };
+
+    let south = GamepadButton::new(Gamepad::new(0), GamepadButtonType::South);
+    let mut cs = ControlScheme::default();
+    cs.insert("Jump", ScanCode(0x39));
+    cs.insert("Jump", south);
+    cs.insert("Crouch", ScanCode(0x1D));
+    let mut app = input_app(cs);
+    app.add_event::<RebindEvent>()
+        .init_resource::<Rebinding>()
+        .add_systems(
+            PreUpdate,
+            action_maps::controls::rebind_system
+                .before(action_maps::input::universal_input_system)
+                .in_set(ActionMapSet::ReadEvents),
+        );
+
+    // a gamepad press is ignored when only keyboard inputs are accepted
+    app.world.resource_mut::<Rebinding>().start(
+        RebindRequest::new("Jump")
+            .with_kind(action_maps::input::InputKind::Keyboard)
+            .with_cancel(ScanCode(0x01)),
+    );
+    app.world
+        .resource_mut::<Events<GamepadButtonChangedEvent>>()
+        .send(GamepadButtonChangedEvent::new(
+            Gamepad::new(0),
+            GamepadButtonType::South,
+            1.,
+        ));
+    app.update();
+    assert!(app.world.resource::<Rebinding>().is_capturing());
+    assert!(!app.world.resource::<ActionInput>().pressed("Jump"));
+
+    // the captured key replaces the old keyboard binding but does not fire actions
+    send_key(&mut app, 0x1D, ButtonState::Pressed);
+    app.update();
+    assert!(!app.world.resource::<Rebinding>().is_capturing());
+    assert!(!app.world.resource::<ActionInput>().pressed("Crouch"));
+    assert!(!app.world.resource::<ActionInput>().pressed("Jump"));
+    assert_eq!(
+        rebind_events(&mut app),
+        vec![RebindEvent::Bound {
+            action: "Jump".into(),
+            input: ScanCode(0x1D).into(),
+            displaced: Some("Crouch".into()),
+        }]
+    );
+    let cs = app.world.resource::<ControlScheme>();
+    assert_eq!(cs.get(ScanCode(0x1D)), Some(&"Jump".into()));
+    assert_eq!(cs.get(ScanCode(0x39)), None);
+    assert_eq!(cs.get(south), Some(&"Jump".into()));
+
+    // the OS repeats of the captured key don't fire its new action
+    send_key(&mut app, 0x1D, ButtonState::Pressed);
+    app.update();
+    assert!(!app.world.resource::<ActionInput>().pressed("Jump"));
+
+    send_key(&mut app, 0x1D, ButtonState::Released);
+    app.update();
+    send_key(&mut app, 0x1D, ButtonState::Pressed);
+    app.update();
+    assert!(app.world.resource::<ActionInput>().just_pressed("Jump"));
+
+    // the cancel input stops capturing without changing bindings
+    app.world
+        .resource_mut::<Rebinding>()
+        .start(RebindRequest::new("Crouch").with_cancel(ScanCode(0x01)));
+    send_key(&mut app, 0x01, ButtonState::Pressed);
+    app.update();
+    assert!(!app.world.resource::<Rebinding>().is_capturing());
+    assert!(!app.world.resource::<ActionInput>().pressed("Jump"));
+    assert_eq!(
+        rebind_events(&mut app),
+        vec![RebindEvent::Cancelled {
+            action: "Crouch".into()
+        }]
+    );
+
+    // capturing times out when nothing is pressed
+    app.world
+        .resource_mut::<Rebinding>()
+        .start(RebindRequest::new("Crouch").with_timeout(Duration::from_millis(100)));
+    app.update();
+    app.world
+        .resource_mut::<Time>()
+        .advance_by(Duration::from_millis(150));
+    app.update();
+    assert!(!app.world.resource::<Rebinding>().is_capturing());
+    assert_eq!(
+        rebind_events(&mut app),
+        vec![RebindEvent::TimedOut {
+            action: "Crouch".into()
+        }]
+    );
+}
+
+#[test]
+fn key_repeats_are_not_new_presses() {
+    use std::time::Duration;
+
+    let mut cs = ControlScheme::default();
+    cs.insert_interaction(
+        "Inspect",
+        ScanCode(0x13),
+        InputInteraction::hold(Duration::from_millis(500)),
+    );
+    cs.insert_interaction(
+        "Dash",
+        ScanCode(0x20),
+        InputInteraction::double_tap(Duration::from_millis(250)),
+    );
+    cs.insert_chord("Save", Chord::new([ScanCode(0x1D)], ScanCode(0x1F)));
+    let mut app = input_app(cs);
+    let repeat = |app: &mut App, scan_code: u32, elapsed: u64| {
+        send_key(app, scan_code, ButtonState::Pressed);
+        app.world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(elapsed));
+        app.update();
+    };
+
+    // a hold completes while the key repeats
+    repeat(&mut app, 0x13, 0);
+    for _ in 0..3 {
+        repeat(&mut app, 0x13, 200);
+    }
+    assert!(app
+        .world
+        .resource::<ActionInput>()
+        .just_triggered("Inspect"));
+    send_key(&mut app, 0x13, ButtonState::Released);
+    app.update();
+
+    // repeats are not counted as taps
+    repeat(&mut app, 0x20, 0);
+    repeat(&mut app, 0x20, 30);
+    assert!(!app.world.resource::<ActionInput>().just_triggered("Dash"));
+    send_key(&mut app, 0x20, ButtonState::Released);
+    app.update();
+
+    // a repeated chord trigger is released along with the chord
+    send_key(&mut app, 0x1D, ButtonState::Pressed);
+    repeat(&mut app, 0x1F, 0);
+    repeat(&mut app, 0x1F, 30);
+    repeat(&mut app, 0x1F, 30);
+    let ai = app.world.resource::<ActionInput>();
+    assert!(ai.pressed("Save"));
+    assert!(!ai.just_pressed("Save"));
+    send_key(&mut app, 0x1F, ButtonState::Released);
+    app.update();
+    assert!(!app.world.resource::<ActionInput>().pressed("Save"));
+}
+
+#[test]
+fn consumed_shared_bindings_do_not_fire() {
+    use action_maps::controls::SharedBinding;
+
+    let mut app = App::new();
+    app.add_plugins(ActionMapPlugin::new());
+    let mut cs = ControlScheme::default();
+    cs.insert_shared(ScanCode(0x39), SharedBinding::new("Confirm"));
+    cs.insert_shared(
+        ScanCode(0x39),
+        SharedBinding::new("Skip").with_priority(2).consuming(),
+    );
+    cs.insert_shared(ScanCode(0x1C), SharedBinding::new("Confirm"));
+    cs.insert_shared(ScanCode(0x1C), SharedBinding::new("Chat").with_priority(1));
+    app.insert_resource(cs);
+    app.update();
+
+    send_key(&mut app, 0x39, ButtonState::Pressed);
+    app.update();
+    let input = app.world.resource::<ActionInput>();
+    assert!(input.just_pressed("Skip"));
+    assert!(!input.pressed("Confirm"));
+    send_key(&mut app, 0x39, ButtonState::Released);
+    app.update();
+    let input = app.world.resource::<ActionInput>();
+    assert_eq!(
+        input.get_just_released().copied().collect::<Vec<_>>(),
+        vec![Action::from("Skip")]
+    );
+
+    // without a consuming binding every shared action fires
+    send_key(&mut app, 0x1C, ButtonState::Pressed);
+    app.update();
+    let input = app.world.resource::<ActionInput>();
+    assert!(input.just_pressed("Chat"));
+    assert!(input.just_pressed("Confirm"));
+}
+
+#[test]
+fn rebinding_keeps_other_kinds_and_ignores_held_keys() {
+    let south = GamepadButton::new(Gamepad::new(0), GamepadButtonType::South);
+    let mut cs = ControlScheme::default();
+    cs.insert("Jump", ScanCode(0x39));
+    cs.insert("Jump", south);
+    let mut app = input_app(cs);
+    app.add_event::<RebindEvent>()
+        .init_resource::<Rebinding>()
+        .add_systems(
+            PreUpdate,
+            action_maps::controls::rebind_system
+                .before(action_maps::input::universal_input_system)
+                .in_set(ActionMapSet::ReadEvents),
+        );
+
+    // the key which opens the rebind menu is still held when capturing starts
+    send_key(&mut app, 0x1C, ButtonState::Pressed);
+    app.update();
+    app.world
+        .resource_mut::<Rebinding>()
+        .start(RebindRequest::new("Jump"));
+    send_key(&mut app, 0x1C, ButtonState::Pressed);
+    app.update();
+    assert!(app.world.resource::<Rebinding>().is_capturing());
+
+    send_key(&mut app, 0x1C, ButtonState::Released);
+    send_key(&mut app, 0x1D, ButtonState::Pressed);
+    app.update();
+    assert!(!app.world.resource::<Rebinding>().is_capturing());
+    assert_eq!(
+        app.world
+            .resource_mut::<Events<RebindEvent>>()
+            .drain()
+            .collect::<Vec<_>>(),
+        vec![RebindEvent::Bound {
+            action: "Jump".into(),
+            input: ScanCode(0x1D).into(),
+            displaced: None,
+        }]
+    );
+    let cs = app.world.resource::<ControlScheme>();
+    assert_eq!(cs.get(ScanCode(0x1D)), Some(&"Jump".into()));
+    assert_eq!(cs.get(ScanCode(0x39)), None);
+    assert_eq!(cs.get(south), Some(&"Jump".into()));
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "asset")]
+
+use action_maps::input::UniversalInput;
+use action_maps::prelude::*;
+use bevy::asset::AssetPlugin;
+use bevy::prelude::*;
+
+#[test]
+fn control_scheme_is_loaded_from_asset() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin {
+            file_path: "tests/assets".to_string(),
+            ..default()
+        },
+        ActionMapPlugin::new()
+            .with_control_scheme_asset("controls/default.controls.ron"),
+    ));
+
+    for _ in 0..1000 {
+        app.update();
+        if app.world.resource::<ControlScheme>() != &ControlScheme::default() {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(1));
+    }
+
+    let cs = app.world.resource::<ControlScheme>();
+    assert_eq!(cs.get(UniversalInput::Space), Some(&Action::from("Jump")));
+    assert_eq!(
+        cs.get(UniversalInput::GamepadSouth(0)),
+        Some(&Action::from("Jump"))
+    );
+    assert_eq!(
+        cs.get(UniversalInput::MouseLeft),
+        Some(&Action::from("Shoot"))
+    );
+    assert_eq!(cs.get_dual_axes("Move").len(), 1);
+}
@@ -0,0 +1,231 @@
+use action_maps::controls::{state_context_name, ActionContext, ContextEvent};
+use action_maps::prelude::*;
+use bevy::prelude::*;
+use bevy_input::{keyboard::KeyboardInput, ButtonState};
+
+fn send_key(app: &mut App, scan_code: u32, state: ButtonState) {
+    app.world
+        .resource_mut::<Events<KeyboardInput>>()
+        .send(KeyboardInput {
+            scan_code,
+            key_code: None,
+            state,
+            window: Entity::from_raw(0),
+        });
+}
+
+fn tap(app: &mut App, scan_code: u32) -> Vec<Action> {
+    send_key(app, scan_code, ButtonState::Pressed);
+    app.update();
+    let pressed = app
+        .world
+        .resource::<ActionInput>()
+        .get_just_pressed()
+        .copied()
+        .collect();
+    send_key(app, scan_code, ButtonState::Released);
+    app.update();
+    pressed
+}
+
+#[test]
+fn contexts_take_inputs_from_the_top_down() {
+    let mut app = App::new();
+    app.add_plugins(ActionMapPlugin::new());
+    app.insert_resource(make_controls!(
+        ("Jump", ScanCode(0x39)),
+        ("Up", ScanCode(0x11)),
+    ));
+    {
+        let mut contexts = app.world.resource_mut::<ActionContexts>();
+        contexts.insert(
+            "Inventory",
+            ActionContext::passthrough(make_controls!(("Use", ScanCode(0x39)))),
+        );
+        contexts.insert(
+            "Pause",
+            ActionContext::blocking(make_controls!(("Resume", ScanCode(0x01)))),
+        );
+    }
+    app.update();
+    assert_eq!(tap(&mut app, 0x39), vec![Action::from("Jump")]);
+
+    app.world
+        .resource_mut::<Events<ContextEvent>>()
+        .send(ContextEvent::Push("Inventory".into()));
+    assert_eq!(tap(&mut app, 0x39), vec![Action::from("Use")]);
+    assert_eq!(tap(&mut app, 0x11), vec![Action::from("Up")]);
+
+    app.world.resource_mut::<ActionContexts>().push("Pause");
+    assert_eq!(tap(&mut app, 0x39), vec![]);
+    assert_eq!(tap(&mut app, 0x11), vec![]);
+    assert_eq!(tap(&mut app, 0x01), vec![Action::from("Resume")]);
+
+    // rebinds of the base scheme are kept while contexts are pushed
+    app.world
+        .resource_mut::<ControlScheme>()
+        .insert("Jump", ScanCode(0x1C));
+    app.world
+        .resource_mut::<Events<ContextEvent>>()
+        .send(ContextEvent::Pop);
+    app.world.resource_mut::<ActionContexts>().pop();
+    assert_eq!(tap(&mut app, 0x1C), vec![Action::from("Jump")]);
+    assert_eq!(tap(&mut app, 0x39), vec![Action::from("Jump")]);
+}
+
+#[derive(States, Debug, Clone, PartialEq, Eq, Hash, Default)]
+enum GameState {
+    #[default]
+    InGame,
+    Paused,
+}
+
+#[test]
+fn state_contexts_follow_the_state_and_release_held_actions() {
+    let mut app = App::new();
+    app.add_plugins(ActionMapPlugin::new())
+        .add_state::<GameState>()
+        .add_state_context(
+            GameState::Paused,
+            ActionContext::blocking(make_controls!(("Resume", ScanCode(0x01)))),
+        );
+    app.insert_resource(make_controls!(("Sprint", ScanCode(0x2A))));
+    app.update();
+
+    send_key(&mut app, 0x2A, ButtonState::Pressed);
+    app.update();
+    assert!(app.world.resource::<ActionInput>().pressed("Sprint"));
+
+    app.world
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Paused);
+    app.update();
+    app.update();
+    assert_eq!(
+        app.world.resource::<ActionContexts>().top(),
+        Some(state_context_name(&GameState::Paused).as_str())
+    );
+    assert!(!app.world.resource::<ActionInput>().pressed("Sprint"));
+    assert_eq!(tap(&mut app, 0x01), vec![Action::from("Resume")]);
+
+    // the key is let go of while paused
+    send_key(&mut app, 0x2A, ButtonState::Released);
+    app.update();
+    app.world
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::InGame);
+    app.update();
+    app.update();
+    assert_eq!(app.world.resource::<ActionContexts>().top(), None);
+    assert_eq!(tap(&mut app, 0x2A), vec![Action::from("Sprint")]);
+}
+
+#[test]
+fn context_switches_report_released_actions() {
+    let mut app = App::new();
+    app.add_plugins(ActionMapPlugin::new());
+    app.insert_resource(make_controls!(("Sprint", ScanCode(0x2A))));
+    app.world.resource_mut::<ActionContexts>().insert(
+        "Inventory",
+        ActionContext::passthrough(make_controls!(("Use", ScanCode(0x39)))),
+    );
+    send_key(&mut app, 0x2A, ButtonState::Pressed);
+    app.update();
+    assert!(app.world.resource::<ActionInput>().pressed("Sprint"));
+
+    app.world.resource_mut::<ActionContexts>().push("Inventory");
+    app.update();
+    let input = app.world.resource::<ActionInput>();
+    assert!(!input.pressed("Sprint"));
+    assert_eq!(
+        input.get_just_released().copied().collect::<Vec<_>>(),
+        vec![Action::from("Sprint")]
+    );
+    app.update();
+    assert_eq!(
+        app.world
+            .resource::<ActionInput>()
+            .get_just_released()
+            .count(),
+        0
+    );
+
+    send_key(&mut app, 0x2A, ButtonState::Released);
+    send_key(&mut app, 0x2A, ButtonState::Pressed);
+    app.update();
+    assert!(app.world.resource::<ActionInput>().just_pressed("Sprint"));
+
+    app.world.resource_mut::<ActionContexts>().pop();
+    app.update();
+    let input = app.world.resource::<ActionInput>();
+    assert!(!input.pressed("Sprint"));
+    assert_eq!(
+        input.get_just_released().copied().collect::<Vec<_>>(),
+        vec![Action::from("Sprint")]
+    );
+}
+
+#[derive(States, Debug, Clone, PartialEq, Eq, Hash, Default)]
+enum MenuState {
+    #[default]
+    Closed,
+    Paused,
+}
+
+#[test]
+fn state_contexts_of_different_states_are_kept_apart() {
+    let mut app = App::new();
+    app.add_plugins(ActionMapPlugin::new())
+        .add_state::<GameState>()
+        .add_state::<MenuState>()
+        .add_state_context(
+            GameState::Paused,
+            ActionContext::blocking(make_controls!(("Resume", ScanCode(0x01)))),
+        )
+        .add_state_context(
+            MenuState::Paused,
+            ActionContext::blocking(make_controls!(("Back", ScanCode(0x01)))),
+        );
+    app.update();
+
+    app.world
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Paused);
+    app.update();
+    app.update();
+    assert_eq!(tap(&mut app, 0x01), vec![Action::from("Resume")]);
+
+    app.world
+        .resource_mut::<NextState<MenuState>>()
+        .set(MenuState::Paused);
+    app.update();
+    app.update();
+    assert_eq!(app.world.resource::<ActionContexts>().stack().len(), 2);
+    assert_eq!(tap(&mut app, 0x01), vec![Action::from("Back")]);
+}
+
+#[test]
+fn keys_held_across_a_context_switch_do_not_repeat_into_it() {
+    let mut app = App::new();
+    app.add_plugins(ActionMapPlugin::new());
+    app.insert_resource(make_controls!(("Pause", ScanCode(0x01))));
+    app.world.resource_mut::<ActionContexts>().insert(
+        "Pause",
+        ActionContext::blocking(make_controls!(("Resume", ScanCode(0x01)))),
+    );
+    send_key(&mut app, 0x01, ButtonState::Pressed);
+    app.update();
+    assert!(app.world.resource::<ActionInput>().just_pressed("Pause"));
+
+    // the OS repeats the held key after the pause menu opened
+    app.world.resource_mut::<ActionContexts>().push("Pause");
+    for _ in 0..3 {
+        send_key(&mut app, 0x01, ButtonState::Pressed);
+        app.update();
+        assert_eq!(app.world.resource::<ActionInput>().get_pressed().len(), 0);
+    }
+
+    send_key(&mut app, 0x01, ButtonState::Released);
+    app.update();
+    assert_eq!(tap(&mut app, 0x01), vec![Action::from("Resume")]);
+}
@@ -0,0 +1,90 @@
+#![cfg(feature = "derive")]
+
+use action_maps::prelude::*;
+use bevy::prelude::*;
+
+#[derive(Actionlike, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Actions {
+    Up,
+    Down,
+    Jump,
+}
+
+#[test]
+fn derived_actions_convert_both_ways() {
+    assert_eq!(
+        Actions::variants(),
+        &[Actions::Up, Actions::Down, Actions::Jump]
+    );
+
+    for variant in Actions::variants() {
+        let action = Action::from(*variant);
+        assert_eq!(action, variant.action());
+        assert_eq!(action.name(), variant.name());
+        assert_eq!(Actions::from_action(action), Some(*variant));
+        assert_eq!(Actions::from_name(action.name()), Some(*variant));
+    }
+    assert_eq!(Actions::from_name("Crouch"), None);
+    assert_eq!(Actions::from_action(Action::from("Crouch")), None);
+}
+
+#[test]
+fn derived_actions_bind_like_actions() {
+    let mut controls = ControlScheme::new();
+    controls.insert(Actions::Jump, ScanCode(0x39));
+
+    let action = *controls.get(ScanCode(0x39)).unwrap();
+    assert_eq!(Actions::from_action(action), Some(Actions::Jump));
+}
+
+#[test]
+fn typed_plugin_dispatches_enum_actions() {
+    let mut app = App::new();
+    app.add_plugins(ActionMapPlugin::<Actions>::default());
+    app.world
+        .resource_mut::<ControlScheme<Actions>>()
+        .insert(Actions::Jump, ScanCode(0x39));
+    app.update();
+
+    app.world
+        .resource_mut::<Events<bevy::input::keyboard::KeyboardInput>>()
+        .send(bevy::input::keyboard::KeyboardInput {
+            scan_code: 0x39,
+            key_code: None,
+            state: bevy::input::ButtonState::Pressed,
+            window: Entity::from_raw(0),
+        });
+    app.update();
+
+    let input = app.world.resource::<ActionInput<Actions>>();
+    assert!(input.just_pressed(Actions::Jump));
+    assert!(!input.pressed(Actions::Up));
+    assert!(!app.world.contains_resource::<ActionInput>());
+}
+
+#[test]
+fn context_events_only_change_their_action_type() {
+    use action_maps::controls::{ActionContext, ContextEvent};
+
+    let mut app = App::new();
+    app.add_plugins((
+        ActionMapPlugin::new(),
+        ActionMapPlugin::<Actions>::default(),
+    ));
+    app.world
+        .resource_mut::<ActionContexts<Actions>>()
+        .insert("Menu", ActionContext::blocking(ControlScheme::empty()));
+    app.world
+        .resource_mut::<ActionContexts>()
+        .insert("Menu", ActionContext::blocking(ControlScheme::new()));
+
+    app.world
+        .resource_mut::<Events<ContextEvent<Actions>>>()
+        .send(ContextEvent::Push("Menu".into()));
+    app.update();
+    assert_eq!(
+        app.world.resource::<ActionContexts<Actions>>().top(),
+        Some("Menu")
+    );
+    assert_eq!(app.world.resource::<ActionContexts>().top(), None);
+}
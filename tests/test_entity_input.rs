@@ -0,0 +1,191 @@
+use action_maps::prelude::*;
+use bevy::prelude::*;
+use bevy_input::gamepad::GamepadAxisChangedEvent;
+use bevy_input::{keyboard::KeyboardInput, ButtonState};
+
+fn send_key(app: &mut App, scan_code: u32, state: ButtonState) {
+    app.world
+        .resource_mut::<Events<KeyboardInput>>()
+        .send(KeyboardInput {
+            scan_code,
+            key_code: None,
+            state,
+            window: Entity::from_raw(0),
+        });
+}
+
+#[test]
+fn entity_inputs_follow_possession() {
+    let mut app = App::new();
+    app.add_plugins(ActionMapPlugin::new());
+    let player = app
+        .world
+        .spawn((
+            make_controls!(("Forward", ScanCode(0x11))),
+            ActionInput::new(),
+        ))
+        .id();
+    let vehicle = app.world.spawn_empty().id();
+    let bystander = app
+        .world
+        .spawn((
+            make_controls!(("Forward", ScanCode(0x48))),
+            ActionInput::new(),
+        ))
+        .id();
+
+    send_key(&mut app, 0x11, ButtonState::Pressed);
+    app.update();
+    assert!(app
+        .world
+        .get::<ActionInput>(player)
+        .unwrap()
+        .just_pressed("Forward"));
+    assert!(!app
+        .world
+        .get::<ActionInput>(bystander)
+        .unwrap()
+        .pressed("Forward"));
+    assert!(!app.world.resource::<ActionInput>().pressed("Forward"));
+
+    // the player gets into the vehicle while holding the key
+    let mut player_entity = app.world.entity_mut(player);
+    let controls = player_entity.take::<ControlScheme>().unwrap();
+    let input = player_entity.take::<ActionInput>().unwrap();
+    app.world.entity_mut(vehicle).insert((controls, input));
+    app.update();
+    let input = app.world.get::<ActionInput>(vehicle).unwrap();
+    assert!(input.pressed("Forward"));
+    assert!(!input.just_pressed("Forward"));
+
+    send_key(&mut app, 0x11, ButtonState::Released);
+    app.update();
+    let input = app.world.get::<ActionInput>(vehicle).unwrap();
+    assert_eq!(input.get_just_released().count(), 1);
+
+    // removing the controls lets go of held actions
+    send_key(&mut app, 0x11, ButtonState::Pressed);
+    app.update();
+    app.world.entity_mut(vehicle).remove::<ControlScheme>();
+    app.update();
+    let input = app.world.get::<ActionInput>(vehicle).unwrap();
+    assert!(!input.pressed("Forward"));
+    assert_eq!(input.get_just_released().count(), 1);
+}
+
+#[test]
+fn removing_the_controls_resets_axes() {
+    let gamepad = Gamepad::new(0);
+    let mut app = App::new();
+    app.add_plugins(ActionMapPlugin::new());
+    let player = app
+        .world
+        .spawn((
+            make_controls!((
+                "MoveX",
+                GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX)
+            )),
+            ActionInput::new(),
+        ))
+        .id();
+
+    app.world
+        .resource_mut::<Events<GamepadAxisChangedEvent>>()
+        .send(GamepadAxisChangedEvent::new(
+            gamepad,
+            GamepadAxisType::LeftStickX,
+            -0.5,
+        ));
+    app.update();
+    assert_eq!(
+        app.world.get::<ActionInput>(player).unwrap().axis("MoveX"),
+        -0.5
+    );
+
+    app.world.entity_mut(player).remove::<ControlScheme>();
+    app.update();
+    assert_eq!(
+        app.world.get::<ActionInput>(player).unwrap().axis("MoveX"),
+        0.
+    );
+}
+
+#[test]
+fn entity_inputs_are_suspended_while_rebinding() {
+    let mut app = App::new();
+    app.add_plugins(ActionMapPlugin::new());
+    let player = app
+        .world
+        .spawn((
+            make_controls!(("Forward", ScanCode(0x11))),
+            ActionInput::new(),
+        ))
+        .id();
+    app.update();
+
+    app.world
+        .resource_mut::<Rebinding>()
+        .start(RebindRequest::new("Jump"));
+    send_key(&mut app, 0x11, ButtonState::Pressed);
+    app.update();
+    assert_eq!(
+        app.world.resource::<ControlScheme>().get(ScanCode(0x11)),
+        Some(&"Jump".into())
+    );
+    assert!(!app
+        .world
+        .get::<ActionInput>(player)
+        .unwrap()
+        .pressed("Forward"));
+
+    send_key(&mut app, 0x11, ButtonState::Released);
+    app.update();
+    send_key(&mut app, 0x11, ButtonState::Pressed);
+    app.update();
+    assert!(app
+        .world
+        .get::<ActionInput>(player)
+        .unwrap()
+        .just_pressed("Forward"));
+}
+
+#[test]
+fn entity_inputs_are_suspended_by_blocking_contexts() {
+    use action_maps::controls::ActionContext;
+
+    let mut app = App::new();
+    app.add_plugins(ActionMapPlugin::new());
+    let player = app
+        .world
+        .spawn((
+            make_controls!(("Forward", ScanCode(0x11))),
+            ActionInput::new(),
+        ))
+        .id();
+    app.world.resource_mut::<ActionContexts>().insert(
+        "Pause",
+        ActionContext::blocking(make_controls!(("Resume", ScanCode(0x01)))),
+    );
+    send_key(&mut app, 0x11, ButtonState::Pressed);
+    app.update();
+    assert!(app
+        .world
+        .get::<ActionInput>(player)
+        .unwrap()
+        .pressed("Forward"));
+
+    app.world.resource_mut::<ActionContexts>().push("Pause");
+    app.update();
+    let input = app.world.get::<ActionInput>(player).unwrap();
+    assert!(!input.pressed("Forward"));
+    assert_eq!(input.get_just_released().count(), 1);
+
+    send_key(&mut app, 0x11, ButtonState::Released);
+    send_key(&mut app, 0x11, ButtonState::Pressed);
+    app.update();
+    assert!(!app
+        .world
+        .get::<ActionInput>(player)
+        .unwrap()
+        .pressed("Forward"));
+}
@@ -1,3 +1,4 @@
+use action_maps::actions::JoinDevice;
 use action_maps::multiplayer_prelude::*;
 use bevy::prelude::*;
 use bevy_input::{keyboard::KeyboardInput, ButtonState};
@@ -12,9 +13,13 @@ fn multi_resource_responds_to_update() {
     )
     .add_event::<bevy::input::keyboard::KeyboardInput>()
     .add_event::<bevy::input::gamepad::GamepadButtonChangedEvent>()
+    .add_event::<bevy::input::gamepad::GamepadAxisChangedEvent>()
     .add_event::<bevy::input::gamepad::GamepadButtonInput>()
     .add_event::<bevy::input::mouse::MouseButtonInput>()
+    .add_event::<bevy::input::mouse::MouseMotion>()
+    .add_event::<bevy::input::mouse::MouseWheel>()
     .insert_resource(bevy::input::gamepad::GamepadSettings::default())
+    .init_resource::<Time>()
     .add_systems(
         PreUpdate,
         (action_maps::input::multi_universal_input_system)
@@ -79,3 +84,420 @@ fn multi_resource_responds_to_update() {
     assert!(!mi.get(0).unwrap().pressed("Left"));
     assert!(mi.get(1).unwrap().pressed("LeftArrow"));
 }
+
+#[test]
+fn player_gamepad_bindings_follow_assigned_gamepads() {
+    use bevy::input::gamepad::{
+        GamepadButtonChangedEvent, GamepadConnection, GamepadConnectionEvent,
+        GamepadInfo,
+    };
+
+    let mut app = App::new();
+    app.add_plugins(MultiActionMapPlugin);
+
+    let jump = GamepadButton::new(PLAYER_GAMEPAD, GamepadButtonType::South);
+    let mut mi = MultiInput::default();
+    let mut ms = MultiScheme::default();
+    make_multi_input!(mi, ms, (("Jump", jump),), (("Jump", jump),));
+    app.insert_resource(mi);
+    app.insert_resource(ms);
+
+    let connect = |id| GamepadConnectionEvent {
+        gamepad: Gamepad::new(id),
+        connection: GamepadConnection::Connected(GamepadInfo {
+            name: String::from("Pad"),
+        }),
+    };
+    let mut connections = app.world.resource_mut::<Events<GamepadConnectionEvent>>();
+    connections.send(connect(7));
+    connections.send(connect(2));
+    app.update();
+
+    app.world
+        .resource_mut::<Events<GamepadButtonChangedEvent>>()
+        .send(GamepadButtonChangedEvent::new(
+            Gamepad::new(2),
+            GamepadButtonType::South,
+            1.0,
+        ));
+    app.update();
+
+    let mi = app.world.resource::<MultiInput>();
+    assert_eq!(mi.gamepad(0), Some(Gamepad::new(7)));
+    assert!(!mi.get(0).unwrap().pressed("Jump"));
+    assert!(mi.get(1).unwrap().pressed("Jump"));
+}
+
+#[test]
+fn lobby_joins_players_from_free_devices() {
+    use bevy::input::gamepad::{
+        GamepadButtonChangedEvent, GamepadConnection, GamepadConnectionEvent,
+        GamepadInfo,
+    };
+
+    let mut app = App::new();
+    app.add_plugins(MultiActionMapPlugin);
+    app.insert_resource(
+        Lobby::new("Join", "Leave")
+            .with_gamepad_template(make_controls!(
+                (
+                    "Join",
+                    GamepadButton::new(PLAYER_GAMEPAD, GamepadButtonType::Start)
+                ),
+                (
+                    "Leave",
+                    GamepadButton::new(PLAYER_GAMEPAD, GamepadButtonType::Select)
+                ),
+            ))
+            .with_keyboard_template(make_controls!(
+                ("Join", ScanCode(0x12)),
+                ("Leave", ScanCode(0x10)),
+            )),
+    );
+    app.world
+        .resource_mut::<Events<GamepadConnectionEvent>>()
+        .send(GamepadConnectionEvent {
+            gamepad: Gamepad::new(3),
+            connection: GamepadConnection::Connected(GamepadInfo {
+                name: String::from("Pad"),
+            }),
+        });
+    app.update();
+    assert_eq!(
+        app.world.resource::<MultiInput>().free_gamepads(),
+        [Gamepad::new(3)]
+    );
+
+    let button = |button_type, value| {
+        GamepadButtonChangedEvent::new(Gamepad::new(3), button_type, value)
+    };
+    let key = |scan_code, state| KeyboardInput {
+        scan_code,
+        key_code: None,
+        state,
+        window: Entity::from_raw(0),
+    };
+    app.world
+        .resource_mut::<Events<GamepadButtonChangedEvent>>()
+        .send(button(GamepadButtonType::Start, 1.0));
+    app.update();
+    app.world
+        .resource_mut::<Events<KeyboardInput>>()
+        .send(key(0x12, ButtonState::Pressed));
+    app.update();
+
+    let lobby = app.world.resource::<Lobby>();
+    assert_eq!(lobby.device(0), Some(JoinDevice::Gamepad(Gamepad::new(3))));
+    assert_eq!(lobby.device(1), Some(JoinDevice::Keyboard(0)));
+    let mi = app.world.resource::<MultiInput>();
+    assert_eq!(mi.gamepad(0), Some(Gamepad::new(3)));
+    assert!(mi.free_gamepads().is_empty());
+    assert!(app.world.resource::<MultiScheme>().get(1).is_some());
+
+    // pressing join again on a taken device does nothing
+    app.world
+        .resource_mut::<Events<KeyboardInput>>()
+        .send(key(0x12, ButtonState::Released));
+    app.update();
+    app.world
+        .resource_mut::<Events<KeyboardInput>>()
+        .send(key(0x12, ButtonState::Pressed));
+    app.update();
+    assert_eq!(app.world.resource::<Lobby>().players().count(), 2);
+
+    app.world
+        .resource_mut::<Events<KeyboardInput>>()
+        .send(key(0x10, ButtonState::Pressed));
+    app.update();
+
+    let lobby = app.world.resource::<Lobby>();
+    assert_eq!(lobby.device(1), None);
+    assert!(app.world.resource::<MultiInput>().get(1).is_none());
+    assert!(app.world.resource::<MultiScheme>().get(1).is_none());
+
+    let events = app.world.resource::<Events<LobbyEvent>>();
+    let events = events
+        .get_reader()
+        .read(events)
+        .cloned()
+        .collect::<Vec<_>>();
+    assert_eq!(
+        events.last(),
+        Some(&LobbyEvent::Left {
+            player: 1,
+            device: JoinDevice::Keyboard(0),
+        })
+    );
+}
+
+#[test]
+fn lobby_joins_keyboard_halves_as_separate_players() {
+    use bevy::input::gamepad::{
+        GamepadConnection, GamepadConnectionEvent, GamepadInfo,
+    };
+
+    let up = action_maps::get_scan_code("Up").unwrap();
+    let mut app = App::new();
+    app.add_plugins(MultiActionMapPlugin);
+    app.insert_resource(
+        Lobby::new("Join", "Leave")
+            .with_keyboard_template(make_controls!(
+                ("Join", ScanCode(0x12)),
+                ("Up", ScanCode(0x11)),
+            ))
+            .with_keyboard_template(make_controls!(
+                ("Join", ScanCode(0x36)),
+                ("Up", ScanCode(up)),
+            )),
+    );
+    app.update();
+
+    send_key(&mut app, 0x12, ButtonState::Pressed);
+    send_key(&mut app, 0x36, ButtonState::Pressed);
+    app.update();
+    let lobby = app.world.resource::<Lobby>();
+    assert_eq!(lobby.device(0), Some(JoinDevice::Keyboard(0)));
+    assert_eq!(lobby.device(1), Some(JoinDevice::Keyboard(1)));
+
+    // each player only reacts to their own half of the keyboard
+    send_key(&mut app, 0x11, ButtonState::Pressed);
+    app.update();
+    let mi = app.world.resource::<MultiInput>();
+    assert!(mi.get(0).unwrap().pressed("Up"));
+    assert!(!mi.get(1).unwrap().pressed("Up"));
+    send_key(&mut app, 0x11, ButtonState::Released);
+    send_key(&mut app, up, ButtonState::Pressed);
+    app.update();
+    let mi = app.world.resource::<MultiInput>();
+    assert!(!mi.get(0).unwrap().pressed("Up"));
+    assert!(mi.get(1).unwrap().pressed("Up"));
+
+    // gamepads are only assigned by joining while the lobby exists
+    let connect = |app: &mut App, id| {
+        app.world
+            .resource_mut::<Events<GamepadConnectionEvent>>()
+            .send(GamepadConnectionEvent {
+                gamepad: Gamepad::new(id),
+                connection: GamepadConnection::Connected(GamepadInfo {
+                    name: String::from("Pad"),
+                }),
+            });
+        app.update();
+    };
+    connect(&mut app, 2);
+    assert_eq!(app.world.resource::<MultiInput>().gamepad(0), None);
+    app.world.remove_resource::<Lobby>();
+    connect(&mut app, 3);
+    let mi = app.world.resource::<MultiInput>();
+    assert!(mi.auto_assign_gamepads());
+    assert_eq!(mi.gamepad(0), Some(Gamepad::new(3)));
+}
+
+#[test]
+fn lobby_max_players_counts_players_added_without_joining() {
+    let mut app = App::new();
+    app.add_plugins(MultiActionMapPlugin);
+    app.insert_resource(
+        Lobby::new("Join", "Leave")
+            .with_keyboard_template(make_controls!(("Join", ScanCode(0x12))))
+            .with_keyboard_template(make_controls!(("Join", ScanCode(0x36))))
+            .with_max_players(2),
+    );
+    app.world.resource_mut::<MultiInput>().insert(0);
+    app.update();
+
+    send_key(&mut app, 0x12, ButtonState::Pressed);
+    app.update();
+    send_key(&mut app, 0x36, ButtonState::Pressed);
+    app.update();
+    let lobby = app.world.resource::<Lobby>();
+    assert_eq!(lobby.device(1), Some(JoinDevice::Keyboard(0)));
+    assert_eq!(lobby.player(JoinDevice::Keyboard(1)), None);
+    assert_eq!(app.world.resource::<MultiInput>().keys().len(), 2);
+}
+
+fn send_key(app: &mut App, scan_code: u32, state: ButtonState) {
+    app.world
+        .resource_mut::<Events<KeyboardInput>>()
+        .send(KeyboardInput {
+            scan_code,
+            key_code: None,
+            state,
+            window: Entity::from_raw(0),
+        });
+}
+
+#[test]
+fn sparse_player_ids_are_updated() {
+    let mut app = App::new();
+    app.add_plugins(MultiActionMapPlugin);
+    let mut mi = MultiInput::default();
+    let mut ms = MultiScheme::default();
+    for (id, scan_code) in [(3, 0x11), (10, 0x12), (42, 0x13)] {
+        mi.insert(id);
+        ms.insert(id, make_controls!(("Jump", ScanCode(scan_code))));
+    }
+    app.insert_resource(mi);
+    app.insert_resource(ms);
+    app.update();
+
+    send_key(&mut app, 0x12, ButtonState::Pressed);
+    send_key(&mut app, 0x13, ButtonState::Pressed);
+    app.update();
+
+    let mi = app.world.resource::<MultiInput>();
+    assert!(!mi.get(3).unwrap().pressed("Jump"));
+    assert!(mi.get(10).unwrap().just_pressed("Jump"));
+    assert!(mi.get(42).unwrap().just_pressed("Jump"));
+}
+
+#[test]
+fn players_without_schemes_are_unbound() {
+    let mut app = App::new();
+    app.add_plugins(MultiActionMapPlugin);
+    let mut mi = MultiInput::default();
+    let mut ms = MultiScheme::default();
+    mi.has_players(2);
+    ms.insert(1, make_controls!(("Jump", ScanCode(0x11))));
+    app.insert_resource(mi);
+    app.insert_resource(ms);
+
+    send_key(&mut app, 0x11, ButtonState::Pressed);
+    app.update();
+    let mi = app.world.resource::<MultiInput>();
+    assert_eq!(mi.get(0).unwrap().get_pressed().count(), 0);
+    assert!(mi.get(1).unwrap().pressed("Jump"));
+
+    // a scheme given later is picked up
+    app.world
+        .resource_mut::<MultiScheme>()
+        .insert(0, make_controls!(("Jump", ScanCode(0x12))));
+    send_key(&mut app, 0x12, ButtonState::Pressed);
+    app.update();
+    assert!(app
+        .world
+        .resource::<MultiInput>()
+        .get(0)
+        .unwrap()
+        .pressed("Jump"));
+}
+
+#[test]
+fn players_can_be_removed_mid_game() {
+    let mut app = App::new();
+    app.add_plugins(MultiActionMapPlugin);
+    let mut mi = MultiInput::default();
+    let mut ms = MultiScheme::default();
+    make_multi_input!(
+        mi,
+        ms,
+        (("Jump", ScanCode(0x11)),),
+        (("Jump", ScanCode(0x12)),),
+        (("Jump", ScanCode(0x13)),)
+    );
+    app.insert_resource(mi);
+    app.insert_resource(ms);
+
+    send_key(&mut app, 0x12, ButtonState::Pressed);
+    app.update();
+    assert!(app
+        .world
+        .resource::<MultiInput>()
+        .get(1)
+        .unwrap()
+        .pressed("Jump"));
+
+    app.world.resource_mut::<MultiInput>().remove(1);
+    send_key(&mut app, 0x12, ButtonState::Released);
+    send_key(&mut app, 0x13, ButtonState::Pressed);
+    app.update();
+
+    let mi = app.world.resource::<MultiInput>();
+    assert!(mi.get(1).is_none());
+    assert!(mi.get(2).unwrap().pressed("Jump"));
+
+    // the scheme of a removed player is ignored, and a rejoining player starts fresh
+    app.world.resource_mut::<MultiInput>().insert(1);
+    app.update();
+    let mi = app.world.resource::<MultiInput>();
+    assert!(!mi.get(1).unwrap().pressed("Jump"));
+    assert!(mi.get(2).unwrap().pressed("Jump"));
+}
+
+#[test]
+fn global_actions_work_alongside_players() {
+    let mut app = App::new();
+    app.add_plugins((ActionMapPlugin::new(), MultiActionMapPlugin));
+    app.insert_resource(make_controls!(("Pause", ScanCode(0x01))));
+    let mut mi = MultiInput::default();
+    let mut ms = MultiScheme::default();
+    make_multi_input!(
+        mi,
+        ms,
+        (("Jump", ScanCode(0x11)),),
+        (("Jump", ScanCode(0x12)),)
+    );
+    app.insert_resource(mi);
+    app.insert_resource(ms);
+    app.world
+        .spawn((make_controls!(("Jump", ScanCode(0x11))), ActionInput::new()));
+
+    send_key(&mut app, 0x01, ButtonState::Pressed);
+    send_key(&mut app, 0x12, ButtonState::Pressed);
+    app.update();
+
+    assert!(app.world.resource::<ActionInput>().just_pressed("Pause"));
+    let mi = app.world.resource::<MultiInput>();
+    assert!(!mi.any_player_pressed("Pause"));
+    assert!(mi.any_player_just_pressed("Jump"));
+    assert_eq!(mi.players_just_pressed("Jump"), vec![1]);
+
+    // entity inputs are updated once even though both plugins use `Action`
+    send_key(&mut app, 0x11, ButtonState::Pressed);
+    app.update();
+    let mut query = app.world.query::<&ActionInput>();
+    let input = query.single(&app.world);
+    assert!(input.just_pressed("Jump"));
+    assert_eq!(
+        app.world.resource::<MultiInput>().players_pressed("Jump"),
+        vec![0, 1]
+    );
+}
+
+#[test]
+fn gamepad_buttons_are_forwarded_once_per_event() {
+    use bevy::input::gamepad::{GamepadButtonChangedEvent, GamepadButtonInput};
+
+    let south = GamepadButton::new(Gamepad::new(0), GamepadButtonType::South);
+    let mut app = App::new();
+    app.add_plugins((ActionMapPlugin::new(), MultiActionMapPlugin));
+    app.insert_resource(make_controls!(("Confirm", south)));
+    let mut mi = MultiInput::default();
+    let mut ms = MultiScheme::default();
+    make_multi_input!(mi, ms, (("Jump", south),), (("Jump", south),));
+    app.insert_resource(mi);
+    app.insert_resource(ms);
+
+    let press = |app: &mut App, value| {
+        app.world
+            .resource_mut::<Events<GamepadButtonChangedEvent>>()
+            .send(GamepadButtonChangedEvent::new(
+                south.gamepad,
+                south.button_type,
+                value,
+            ));
+        app.update();
+    };
+    press(&mut app, 1.);
+    press(&mut app, 0.);
+
+    let events = app.world.resource::<Events<GamepadButtonInput>>();
+    // both frames of events are still buffered
+    let forwarded = events
+        .get_reader()
+        .read(events)
+        .map(|event| event.state)
+        .collect::<Vec<_>>();
+    assert_eq!(forwarded, vec![ButtonState::Pressed, ButtonState::Released]);
+    assert!(app.world.resource::<ActionInput>().just_released("Confirm"));
+}
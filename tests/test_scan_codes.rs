@@ -0,0 +1,244 @@
+use action_maps::input::{
+    ActiveScanCodeTable, InputKind, LinuxScanCodes, MacOsScanCodes, ScanCodeTable,
+    UniversalInput, WindowsScanCodes,
+};
+use action_maps::prelude::*;
+use action_maps::{get_key, get_scan_code, KeyError};
+use bevy::input::gamepad::{
+    GamepadAxisChangedEvent, GamepadButtonChangedEvent, GamepadButtonInput,
+    GamepadSettings,
+};
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input::mouse::{MouseButtonInput, MouseMotion, MouseWheel};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+use bevy::reflect::{DynamicEnum, FromReflect, TypeInfo, Typed, VariantInfo};
+
+/// Keyboard inputs which only exist as virtual keys, without a scan code on any OS.
+const VIRTUAL_ONLY: &[&str] = &[
+    "Caret",
+    "Compose",
+    "AbntC2",
+    "Asterisk",
+    "Plus",
+    "At",
+    "Ax",
+    "Colon",
+    "Underline",
+    "Unlabeled",
+    "NavigateForward",
+    "NavigateBackward",
+];
+
+const MACOS_UNMAPPED: &[&str] = &[
+    "F21",
+    "F22",
+    "F23",
+    "F24",
+    "Snapshot",
+    "Scroll",
+    "Pause",
+    "Calculator",
+    "Convert",
+    "Kanji",
+    "Mail",
+    "MediaSelect",
+    "MediaStop",
+    "MyComputer",
+    "NextTrack",
+    "PlayPause",
+    "Power",
+    "PrevTrack",
+    "Sleep",
+    "Stop",
+    "Sysrq",
+    "Wake",
+    "WebBack",
+    "WebFavorites",
+    "WebForward",
+    "WebHome",
+    "WebRefresh",
+    "WebSearch",
+    "WebStop",
+    "Copy",
+    "Paste",
+    "Cut",
+];
+
+const WINDOWS_UNMAPPED: &[&str] = &["Kanji", "Stop"];
+
+const LINUX_UNMAPPED: &[&str] = &["Sysrq", "WebStop"];
+
+fn keyboard_variants() -> Vec<(&'static str, UniversalInput)> {
+    let TypeInfo::Enum(info) = UniversalInput::type_info() else {
+        unreachable!();
+    };
+    info.iter()
+        .filter_map(|variant| match variant {
+            VariantInfo::Unit(unit) => Some(unit.name()),
+            _ => None,
+        })
+        .map(|name| {
+            let input =
+                UniversalInput::from_reflect(&DynamicEnum::new(name, ())).unwrap();
+            (name, input)
+        })
+        .filter(|(_, input)| input.kind() == InputKind::Keyboard)
+        .collect()
+}
+
+/// Checks that every keyboard variant not listed as unmapped round trips through the
+/// table, and that the unmapped ones really are missing.
+fn assert_round_trips(table: &dyn ScanCodeTable, unmapped: &[&str]) {
+    let variants = keyboard_variants();
+    assert!(!variants.is_empty());
+
+    for (name, input) in variants {
+        if VIRTUAL_ONLY.contains(&name) || unmapped.contains(&name) {
+            assert!(
+                table.scan_code(name).is_err(),
+                "{name} should not be mapped"
+            );
+            continue;
+        }
+        let scan_code = table
+            .scan_code(name)
+            .unwrap_or_else(|e| panic!("{name} has no scan code: {e}"));
+        assert_eq!(table.key(scan_code).unwrap(), input, "{name}");
+    }
+}
+
+#[test]
+fn macos_keyboard_variants_round_trip() {
+    assert_round_trips(&MacOsScanCodes, MACOS_UNMAPPED);
+}
+
+#[test]
+fn windows_keyboard_variants_round_trip() {
+    assert_round_trips(&WindowsScanCodes, WINDOWS_UNMAPPED);
+}
+
+#[test]
+fn linux_keyboard_variants_round_trip() {
+    assert_round_trips(&LinuxScanCodes, LINUX_UNMAPPED);
+}
+
+#[test]
+fn aliases_match_variant_names() {
+    let tables: [&dyn ScanCodeTable; 3] =
+        [&MacOsScanCodes, &WindowsScanCodes, &LinuxScanCodes];
+    for table in tables {
+        assert_eq!(
+            table.scan_code("Esc").unwrap(),
+            table.scan_code("Escape").unwrap()
+        );
+        assert_eq!(
+            table.scan_code("Enter").unwrap(),
+            table.scan_code("Return").unwrap()
+        );
+    }
+    assert_eq!(
+        WindowsScanCodes.scan_code("LShift").unwrap(),
+        WindowsScanCodes.scan_code("ShiftLeft").unwrap()
+    );
+}
+
+#[test]
+fn os_tables_can_be_used_on_any_host() {
+    assert_eq!(MacOsScanCodes.scan_code("W").unwrap(), 0x0D);
+    assert_eq!(MacOsScanCodes.key(0x0D).unwrap(), UniversalInput::W);
+    assert_eq!(WindowsScanCodes.scan_code("W").unwrap(), 0x11);
+    assert_eq!(WindowsScanCodes.key(0x11).unwrap(), UniversalInput::W);
+    assert_eq!(LinuxScanCodes.scan_code("W").unwrap(), 0x11);
+    assert!(matches!(
+        MacOsScanCodes.key(0xFFFF),
+        Err(KeyError::ScanCodeNotFound(_))
+    ));
+}
+
+struct SwappedWS;
+
+impl ScanCodeTable for SwappedWS {
+    fn scan_code(&self, key: &str) -> Result<u32, KeyError> {
+        match key {
+            "W" => Ok(1),
+            "S" => Ok(2),
+            key => Err(KeyError::KeyNotFound(key.to_string())),
+        }
+    }
+
+    fn key(&self, scan_code: u32) -> Result<UniversalInput, KeyError> {
+        match scan_code {
+            1 => Ok(UniversalInput::S),
+            2 => Ok(UniversalInput::W),
+            sc => Err(KeyError::ScanCodeNotFound(sc.to_string())),
+        }
+    }
+}
+
+#[test]
+fn tables_convert_without_being_applied() {
+    let table = ActiveScanCodeTable::new(SwappedWS);
+    assert_eq!(table.input_from_key_code(KeyCode::W), UniversalInput::S);
+    assert_eq!(table.input_from_scan_code(ScanCode(2)), UniversalInput::W);
+    assert_eq!(
+        table.input_from_key_code(KeyCode::Q),
+        UniversalInput::Unknown(0)
+    );
+}
+
+#[test]
+fn each_app_reads_keys_with_its_own_table() {
+    // without the plugin, the resources are never applied to the fallback table
+    let table_app = |table| {
+        let mut app = App::new();
+        app.add_event::<KeyboardInput>()
+            .add_event::<GamepadButtonChangedEvent>()
+            .add_event::<GamepadAxisChangedEvent>()
+            .add_event::<GamepadButtonInput>()
+            .add_event::<MouseButtonInput>()
+            .add_event::<MouseMotion>()
+            .add_event::<MouseWheel>()
+            .init_resource::<GamepadSettings>()
+            .init_resource::<Time>()
+            .insert_resource(table)
+            .insert_resource(make_controls!(("Up", UniversalInput::W)))
+            .insert_resource(ActionInput::default())
+            .add_systems(PreUpdate, action_maps::input::universal_input_system);
+        app
+    };
+    let mut windows = table_app(ActiveScanCodeTable::new(WindowsScanCodes));
+    let mut macos = table_app(ActiveScanCodeTable::new(MacOsScanCodes));
+
+    // 0x11 is W on Windows and T on macOS
+    for app in [&mut windows, &mut macos] {
+        app.world
+            .resource_mut::<Events<KeyboardInput>>()
+            .send(KeyboardInput {
+                scan_code: 0x11,
+                key_code: None,
+                state: ButtonState::Pressed,
+                window: Entity::from_raw(0),
+            });
+        app.update();
+    }
+    assert!(windows.world.resource::<ActionInput>().pressed("Up"));
+    assert!(!macos.world.resource::<ActionInput>().pressed("Up"));
+}
+
+// the fallback table is global, so everything using it is checked in a single test
+#[test]
+fn active_table_routes_conversions() {
+    let mut app = App::new();
+    app.add_plugins(ActionMapPlugin::new())
+        .insert_resource(ActiveScanCodeTable::new(SwappedWS));
+    app.update();
+
+    assert_eq!(get_scan_code("W").unwrap(), 1);
+    assert_eq!(get_key(1).unwrap(), UniversalInput::S);
+    assert_eq!(UniversalInput::from(KeyCode::W), UniversalInput::S);
+    assert_eq!(UniversalInput::from(ScanCode(2)), UniversalInput::W);
+
+    ActiveScanCodeTable::host().apply();
+    assert_ne!(get_key(1).ok(), Some(UniversalInput::S));
+}
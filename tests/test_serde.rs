@@ -0,0 +1,174 @@
+#![cfg(feature = "serde")]
+
+use std::time::Duration;
+
+use action_maps::controls::SharedBinding;
+use action_maps::input::UniversalInput;
+use action_maps::multiplayer_prelude::*;
+use bevy::prelude::*;
+
+fn control_scheme() -> ControlScheme {
+    let gamepad = Gamepad::new(0);
+    let mut cs = make_controls!(
+        ("Jump", UniversalInput::Space),
+        (
+            "Jump",
+            GamepadButton::new(gamepad, GamepadButtonType::South)
+        ),
+        ("Shoot", MouseButton::Left),
+        ("NextWeapon", UniversalInput::MouseWheelUp),
+        (
+            "Throttle",
+            GamepadAxis::new(gamepad, GamepadAxisType::RightZ)
+        ),
+    );
+    cs.insert_chord(
+        "Save",
+        Chord::new(
+            [UniversalInput::ShiftLeft, UniversalInput::ControlLeft],
+            UniversalInput::S,
+        ),
+    );
+    cs.insert_interaction(
+        "Reload",
+        UniversalInput::R,
+        InputInteraction::tap(Duration::from_millis(200)),
+    );
+    cs.insert_interaction(
+        "Inspect",
+        UniversalInput::R,
+        InputInteraction::hold(Duration::from_millis(500)),
+    );
+    cs.insert_dual_axis(
+        "Move",
+        DualAxis::buttons(
+            UniversalInput::W,
+            UniversalInput::S,
+            UniversalInput::A,
+            UniversalInput::D,
+        ),
+    );
+    cs.insert_dual_axis("Move", DualAxis::left_stick(gamepad));
+    cs.insert_shared(UniversalInput::Space, SharedBinding::new("Confirm"));
+    cs.insert_shared(
+        UniversalInput::Space,
+        SharedBinding::new("Skip").with_priority(2).consuming(),
+    );
+    cs
+}
+
+#[test]
+fn control_scheme_round_trips() {
+    let cs = control_scheme();
+
+    let ron = ron::to_string(&cs).unwrap();
+    assert_eq!(cs, ron::from_str::<ControlScheme>(&ron).unwrap());
+
+    let json = serde_json::to_string(&cs).unwrap();
+    assert_eq!(cs, serde_json::from_str::<ControlScheme>(&json).unwrap());
+
+    let toml = toml::to_string(&cs).unwrap();
+    assert_eq!(cs, toml::from_str::<ControlScheme>(&toml).unwrap());
+}
+
+#[test]
+fn control_scheme_output_is_stable() {
+    let json = serde_json::to_string(&control_scheme()).unwrap();
+    let reloaded: ControlScheme = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(json, serde_json::to_string(&control_scheme()).unwrap());
+    assert_eq!(json, serde_json::to_string(&reloaded).unwrap());
+}
+
+#[test]
+fn control_scheme_uses_input_names() {
+    let json = serde_json::to_string(&make_controls!(("Jump", UniversalInput::Space)))
+        .unwrap();
+    assert_eq!(json, r#"{"inputs":{"Jump":["Space"]}}"#);
+
+    let cs: ControlScheme =
+        ron::from_str(r#"(inputs: {"Up": [W, GamepadDPadUp(0)]})"#).unwrap();
+    assert_eq!(cs.get(UniversalInput::W), Some(&Action::from("Up")));
+    assert_eq!(
+        cs.get(UniversalInput::GamepadDPadUp(0)),
+        Some(&Action::from("Up"))
+    );
+}
+
+#[test]
+fn multi_scheme_round_trips() {
+    let mut ms = MultiScheme::default();
+    ms.insert(0, control_scheme());
+    ms.insert(1, make_controls!(("Jump", UniversalInput::Return)));
+
+    let ron = ron::to_string(&ms).unwrap();
+    assert_eq!(ms, ron::from_str::<MultiScheme>(&ron).unwrap());
+
+    let json = serde_json::to_string(&ms).unwrap();
+    assert_eq!(ms, serde_json::from_str::<MultiScheme>(&json).unwrap());
+
+    let toml = toml::to_string(&ms).unwrap();
+    assert_eq!(ms, toml::from_str::<MultiScheme>(&toml).unwrap());
+}
+
+#[test]
+fn player_gamepad_bindings_round_trip() {
+    let mut cs = make_controls!(
+        (
+            "Jump",
+            GamepadButton::new(PLAYER_GAMEPAD, GamepadButtonType::South)
+        ),
+        (
+            "Throttle",
+            GamepadAxis::new(PLAYER_GAMEPAD, GamepadAxisType::RightZ)
+        ),
+    );
+    cs.insert_dual_axis("Move", DualAxis::left_stick(PLAYER_GAMEPAD));
+    let mut ms = MultiScheme::default();
+    ms.insert(0, cs.clone());
+    ms.insert(1, cs.clone());
+
+    let toml = toml::to_string(&cs).unwrap();
+    assert_eq!(cs, toml::from_str::<ControlScheme>(&toml).unwrap());
+
+    let toml = toml::to_string(&ms).unwrap();
+    assert_eq!(ms, toml::from_str::<MultiScheme>(&toml).unwrap());
+
+    let ron = ron::to_string(&ms).unwrap();
+    assert_eq!(ms, ron::from_str::<MultiScheme>(&ron).unwrap());
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Actions {
+    Jump,
+}
+
+impl action_maps::actions::Actionlike for Actions {
+    fn variants() -> &'static [Self] {
+        &[Actions::Jump]
+    }
+
+    fn name(&self) -> &'static str {
+        "Jump"
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        (name == "Jump").then_some(Actions::Jump)
+    }
+}
+
+#[test]
+fn typed_control_scheme_rejects_unknown_actions() {
+    let mut cs = ControlScheme::<Actions>::empty();
+    cs.insert(Actions::Jump, UniversalInput::Space);
+
+    let json = serde_json::to_string(&cs).unwrap();
+    assert_eq!(json, r#"{"inputs":{"Jump":["Space"]}}"#);
+    assert_eq!(
+        cs,
+        serde_json::from_str::<ControlScheme<Actions>>(&json).unwrap()
+    );
+
+    let unknown = r#"{"inputs":{"Crouch":["C"]}}"#;
+    assert!(serde_json::from_str::<ControlScheme<Actions>>(unknown).is_err());
+}